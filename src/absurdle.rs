@@ -0,0 +1,184 @@
+use std::{
+    array,
+    simd::{LaneCount, SupportedLaneCount},
+};
+
+use crate::{partition::bucket, squeeze::grade_counts, Grade, Word, ALL_GREEN, N_GRADES};
+
+/// An adversarial host which never commits to an answer.
+/// After every guess it keeps whichever grade leaves the most answers possible.
+#[derive(Clone, Debug)]
+pub struct Host {
+    candidates: Vec<Word>,
+}
+
+impl Host {
+    pub fn new(answers: &[Word]) -> Host {
+        Host {
+            candidates: answers.to_vec(),
+        }
+    }
+
+    pub fn candidates(&self) -> &[Word] {
+        &self.candidates
+    }
+
+    pub fn guess<const L: usize>(&mut self, guess: Word) -> Grade
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        let (graded, kept) = respond::<L>(guess, &self.candidates);
+        self.candidates = kept;
+        graded
+    }
+}
+
+/// The grade the host gives `guess` and the candidates it keeps.
+/// Ties between equally large buckets go to any grade but all-green, then to the lowest grade.
+pub fn respond<const L: usize>(guess: Word, candidates: &[Word]) -> (Grade, Vec<Word>)
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let mut buckets: [Vec<Word>; N_GRADES] = array::from_fn(|_| Vec::new());
+    bucket::<L>(guess, candidates, &mut buckets);
+    let graded = (0..N_GRADES)
+        .max_by_key(|&g| (buckets[g].len(), g as Grade != ALL_GREEN, N_GRADES - g))
+        .unwrap();
+    (graded as Grade, std::mem::take(&mut buckets[graded]))
+}
+
+/// A sequence of guesses which forces a win, with the grades the host gave.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Solution {
+    pub guesses: Vec<Word>,
+    pub grades: Vec<Grade>,
+}
+
+/// Find the shortest sequences of guesses which force the host to concede, up to `max_turns`
+/// guesses long, returning at most `max_solutions` of them.
+/// Only the `width` guesses leaving the fewest candidates are tried at each turn, so shorter
+/// sequences may exist outside the beam.
+pub fn solve<const L: usize>(
+    words: &[Word],
+    answers: &[Word],
+    width: usize,
+    max_turns: usize,
+    max_solutions: usize,
+) -> Vec<Solution>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let mut searcher = Searcher {
+        words,
+        width,
+        max_solutions,
+        guesses: Vec::new(),
+        grades: Vec::new(),
+        found: Vec::new(),
+    };
+    for turns in 1..=max_turns {
+        searcher.search::<L>(answers, turns);
+        if !searcher.found.is_empty() {
+            break;
+        }
+    }
+    searcher.found
+}
+
+struct Searcher<'a> {
+    words: &'a [Word],
+    width: usize,
+    max_solutions: usize,
+    guesses: Vec<Word>,
+    grades: Vec<Grade>,
+    found: Vec<Solution>,
+}
+
+impl Searcher<'_> {
+    fn search<const L: usize>(&mut self, candidates: &[Word], turns: usize)
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        if self.found.len() >= self.max_solutions || turns == 0 {
+            return;
+        }
+        if let &[answer] = candidates {
+            let mut guesses = self.guesses.clone();
+            guesses.push(answer);
+            let mut grades = self.grades.clone();
+            grades.push(ALL_GREEN);
+            self.found.push(Solution { guesses, grades });
+            return;
+        }
+        if turns == 1 {
+            return;
+        }
+
+        // with two turns left, this guess has to pin down the answer
+        let target = if turns == 2 { 1 } else { candidates.len() - 1 };
+        let mut ranked = self
+            .words
+            .iter()
+            .map(|&w| {
+                (
+                    w,
+                    grade_counts::<L>(w, candidates).into_iter().max().unwrap(),
+                )
+            })
+            .filter(|&(_, worst)| usize::from(worst) <= target)
+            .collect::<Vec<_>>();
+        ranked.sort_by_key(|&(_, worst)| worst);
+        ranked.truncate(self.width);
+
+        for (w, _) in ranked {
+            let (graded, kept) = respond::<L>(w, candidates);
+            self.guesses.push(w);
+            self.grades.push(graded);
+            self.search::<L>(&kept, turns - 1);
+            self.guesses.pop();
+            self.grades.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{squeeze::grade, word_from_str};
+
+    use super::*;
+
+    #[test]
+    fn host_keeps_largest_bucket() {
+        let answers = [
+            "cigar", "rebut", "sissy", "humph", "awake", "blush", "focal",
+        ]
+        .map(|s| word_from_str(s.as_bytes()).unwrap());
+        let guess = word_from_str(b"cigar").unwrap();
+
+        let mut host = Host::new(&answers);
+        let graded = host.guess::<4>(guess);
+        assert_ne!(graded, ALL_GREEN);
+        assert!(host.candidates().len() > 1);
+        for &c in host.candidates() {
+            assert_eq!(grade(guess, c), graded);
+        }
+    }
+
+    #[test]
+    fn solutions_are_forced() {
+        let answers = [
+            "cigar", "rebut", "sissy", "humph", "awake", "blush", "focal", "evade", "naval",
+        ]
+        .map(|s| word_from_str(s.as_bytes()).unwrap());
+
+        let found = solve::<4>(&answers, &answers, 5, 6, 3);
+        assert!(!found.is_empty());
+        for s in found {
+            let mut host = Host::new(&answers);
+            for (&w, &g) in s.guesses.iter().zip(&s.grades) {
+                assert_eq!(host.guess::<4>(w), g);
+            }
+            assert_eq!(s.grades.last(), Some(&ALL_GREEN));
+        }
+    }
+}
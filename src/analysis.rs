@@ -0,0 +1,259 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    simd::{LaneCount, SupportedLaneCount},
+};
+
+use crate::{rules::Rules, Grade, Word};
+
+/// How one guess of a game went, next to what the bot would have played.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Turn {
+    pub guess: Word,
+    pub grade: Grade,
+    pub candidates_before: usize,
+    pub candidates_after: usize,
+    /// The information the guess was expected to give, in bits.
+    pub expected_bits: f32,
+    /// The information the grade actually gave, in bits.
+    pub actual_bits: f32,
+    pub best: Word,
+    pub best_expected_bits: f32,
+    /// The expected information as a percentage of the best guess's.
+    pub skill: f32,
+    /// The percentage of answers which would have left more candidates, counting ties as half.
+    pub luck: f32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    pub turns: Vec<Turn>,
+    pub solved: bool,
+    /// The rules the game was played under.
+    pub rules: Rules,
+}
+
+/// Parse a transcript with one guess per line, each followed by its grade, e.g. `trace bgybb`.
+/// If `answer` is given, grades may be left out and are worked out from it instead.
+pub fn parse_transcript(
+    s: &str,
+    answer: Option<Word>,
+    rules: &Rules,
+) -> Result<Vec<(Word, Grade)>, String> {
+    let mut history = Vec::new();
+    for line in s.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let mut parts = line.split_whitespace();
+        let guess = parts
+            .next()
+            .and_then(|w| rules.word_from_str(w.to_ascii_lowercase().as_bytes()))
+            .ok_or_else(|| format!("expected a guess in {line:?}"))?;
+        let graded = match (parts.next(), answer) {
+            (Some(g), _) => rules
+                .grade_from_str(g.as_bytes())
+                .ok_or_else(|| format!("bad grade in {line:?}"))?,
+            (None, Some(answer)) => rules.grade(guess, answer),
+            (None, None) => return Err(format!("no grade for {line:?}")),
+        };
+        history.push((guess, graded));
+    }
+    Ok(history)
+}
+
+/// Analyse a game given each guess and its grade under `rules`. The bot plays whichever word in
+/// `words` or among the remaining candidates leaves the least entropy, keeping to hard mode if
+/// the rules ask for it.
+pub fn analyze<const L: usize>(
+    history: &[(Word, Grade)],
+    words: &[Word],
+    answers: &[Word],
+    rules: &Rules,
+) -> Report
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let mut candidates = answers.to_vec();
+    let mut turns = Vec::with_capacity(history.len());
+    for (i, &(guess, graded)) in history.iter().enumerate() {
+        if candidates.is_empty() {
+            break;
+        }
+        let n = candidates.len() as f32;
+        let expected_bits = n.log2() - rules.entropy_after::<L>(guess, &candidates);
+        let (best, best_left) = candidates
+            .iter()
+            .chain(words)
+            .filter(|&&w| rules.is_legal(w, &history[..i]))
+            .map(|&w| (w, rules.entropy_after::<L>(w, &candidates)))
+            .fold((guess, n.log2() - expected_bits), |best, next| {
+                if next.1 < best.1 {
+                    next
+                } else {
+                    best
+                }
+            });
+        let best_expected_bits = n.log2() - best_left;
+
+        let after = rules.filter::<L>(guess, graded, &candidates);
+        let counts = rules.grade_counts::<L>(guess, &candidates);
+        let (worse, ties) = counts.iter().fold((0, 0), |(worse, ties), &c| {
+            // weighted by the number of answers giving each grade
+            let c = c as usize;
+            match c.cmp(&after.len()) {
+                Ordering::Greater => (worse + c, ties),
+                Ordering::Equal => (worse, ties + c),
+                Ordering::Less => (worse, ties),
+            }
+        });
+
+        turns.push(Turn {
+            guess,
+            grade: graded,
+            candidates_before: candidates.len(),
+            candidates_after: after.len(),
+            expected_bits,
+            actual_bits: n.log2() - (after.len().max(1) as f32).log2(),
+            best,
+            best_expected_bits,
+            skill: if best_expected_bits > 0.0 {
+                100.0 * expected_bits / best_expected_bits
+            } else {
+                100.0
+            },
+            luck: 100.0 * (worse as f32 + ties as f32 / 2.0) / n,
+        });
+        candidates = after;
+    }
+    Report {
+        turns,
+        solved: history.last().is_some_and(|&(_, g)| g == rules.all_green()),
+        rules: *rules,
+    }
+}
+
+impl Report {
+    /// The average skill over the guesses which had a choice to make.
+    pub fn skill(&self) -> f32 {
+        average(
+            self.turns
+                .iter()
+                .filter(|t| t.candidates_before > 1)
+                .map(|t| t.skill),
+        )
+    }
+
+    /// The average luck over the guesses which had a choice to make.
+    pub fn luck(&self) -> f32 {
+        average(
+            self.turns
+                .iter()
+                .filter(|t| t.candidates_before > 1)
+                .map(|t| t.luck),
+        )
+    }
+
+    pub fn to_json(&self) -> String {
+        let show = |w: Word| self.rules.str_from_word(w);
+        let turns = self
+            .turns
+            .iter()
+            .map(|t| {
+                format!(
+                    concat!(
+                        r#"{{"guess":"{}","grade":"{}","candidates_before":{},"#,
+                        r#""candidates_after":{},"expected_bits":{:.3},"actual_bits":{:.3},"#,
+                        r#""best":"{}","best_expected_bits":{:.3},"skill":{:.1},"luck":{:.1}}}"#
+                    ),
+                    show(t.guess),
+                    self.rules.str_from_grade(t.grade),
+                    t.candidates_before,
+                    t.candidates_after,
+                    t.expected_bits,
+                    t.actual_bits,
+                    show(t.best),
+                    t.best_expected_bits,
+                    t.skill,
+                    t.luck
+                )
+            })
+            .collect::<Vec<_>>();
+        format!(
+            r#"{{"solved":{},"skill":{:.1},"luck":{:.1},"turns":[{}]}}"#,
+            self.solved,
+            self.skill(),
+            self.luck(),
+            turns.join(",")
+        )
+    }
+}
+
+fn average(xs: impl Iterator<Item = f32>) -> f32 {
+    let (sum, n) = xs.fold((0.0, 0), |(sum, n), x| (sum + x, n + 1));
+    if n == 0 {
+        100.0
+    } else {
+        sum / n as f32
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |w: Word| self.rules.str_from_word(w);
+        for t in &self.turns {
+            write!(
+                f,
+                "{} {}: {} -> {} candidates, {:.2} bits (expected {:.2})",
+                show(t.guess),
+                self.rules.str_from_grade(t.grade),
+                t.candidates_before,
+                t.candidates_after,
+                t.actual_bits,
+                t.expected_bits
+            )?;
+            if t.best != t.guess {
+                write!(
+                    f,
+                    "; bot: {} (expected {:.2})",
+                    show(t.best),
+                    t.best_expected_bits
+                )?;
+            }
+            writeln!(f, "; skill {:.0}, luck {:.0}", t.skill, t.luck)?;
+        }
+        write!(
+            f,
+            "{}; skill {:.0}/100, luck {:.0}/100",
+            if self.solved { "solved" } else { "not solved" },
+            self.skill(),
+            self.luck()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{grade_from_str, word_from_str};
+
+    use super::*;
+
+    #[test]
+    fn perfect_game() {
+        let w = |s: &str| word_from_str(s.as_bytes()).unwrap();
+        let answers = ["crate", "trace", "lions", "cruel"].map(w);
+        let rules = Rules::default();
+        let history = parse_transcript("trace\ncrate\n", Some(w("crate")), &rules).unwrap();
+        assert_eq!(history[0].1, grade_from_str(b"yggyg").unwrap());
+
+        let report = analyze::<8>(&history, &answers, &answers, &rules);
+        assert!(report.solved);
+        assert_eq!(report.turns[0].candidates_before, 4);
+        assert_eq!(report.turns[0].candidates_after, 1);
+        assert_eq!(report.turns[0].actual_bits, 2.0);
+        // every answer gives `trace` a different grade
+        assert_eq!(report.turns[0].skill, 100.0);
+        assert_eq!(report.turns[0].luck, 50.0);
+        assert_eq!(report.turns[1].candidates_before, 1);
+        assert!(report
+            .to_json()
+            .starts_with(r#"{"solved":true,"skill":100.0"#));
+    }
+}
@@ -0,0 +1,80 @@
+#![feature(portable_simd)]
+
+use core::str;
+use std::io::{stdin, BufRead};
+
+use wordle::{
+    absurdle::{solve, Host},
+    read_words, str_from_grade, str_from_word, word_from_str, ALL_GREEN,
+};
+
+const L: usize = 8;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let play = std::env::args().any(|a| a == "--play");
+    let args = std::env::args()
+        .filter(|a| a != "--play")
+        .collect::<Vec<_>>();
+    if args.len() < 3 {
+        println!("absurdle: usage: absurdle <answers> <words> [width] [max_turns] [--play]");
+        return Ok(());
+    }
+    let answers = read_words(&args[1])?;
+    let words = read_words(&args[2])?;
+
+    if play {
+        let mut host = Host::new(&answers);
+        // only accepted guesses count
+        let mut turn = 0;
+        for line in stdin().lock().lines() {
+            let line = line?;
+            let Some(guess) = word_from_str(line.trim().as_bytes()).filter(|w| words.contains(w))
+            else {
+                println!("not a word");
+                continue;
+            };
+            turn += 1;
+            let graded = host.guess::<L>(guess);
+            println!(
+                "{} ({} left)",
+                str::from_utf8(&str_from_grade(graded)).unwrap(),
+                host.candidates().len()
+            );
+            if graded == ALL_GREEN {
+                println!("won in {turn} guesses");
+                break;
+            }
+        }
+        return Ok(());
+    }
+
+    let width = args.get(3).map_or(Ok(10), |s| s.parse())?;
+    let max_turns = args.get(4).map_or(Ok(6), |s| s.parse())?;
+    let found = solve::<L>(&words, &answers, width, max_turns, 100);
+    let Some(shortest) = found.first() else {
+        println!("no win within {max_turns} guesses");
+        return Ok(());
+    };
+    println!(
+        "{} sequences of {} guesses:",
+        found.len(),
+        shortest.guesses.len()
+    );
+    for s in &found {
+        println!(
+            "{}",
+            s.guesses
+                .iter()
+                .zip(&s.grades)
+                .map(|(&w, &g)| format!(
+                    "{} {}",
+                    str::from_utf8(&str_from_word(w)).unwrap(),
+                    str::from_utf8(&str_from_grade(g)).unwrap()
+                ))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    Ok(())
+}
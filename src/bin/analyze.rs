@@ -0,0 +1,54 @@
+#![feature(portable_simd)]
+
+use std::io::{read_to_string, stdin};
+
+use wordle::{
+    analysis::{analyze, parse_transcript},
+    rules::Rules,
+};
+
+const L: usize = 8;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = std::env::args().collect::<Vec<_>>();
+    if args.len() < 3 {
+        println!("analyze: usage: analyze <answers> <words> [transcript] [--rules file] [--answer word] [--hard] [--json]");
+        println!("the transcript has one guess per line with its grade, e.g. `trace bgybb`, and is read from stdin by default");
+        return Ok(());
+    }
+    let mut rules = Rules::default();
+    let mut path = None;
+    let mut answer = None;
+    let mut hard = false;
+    let mut json = false;
+    let mut rest = args[3..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--rules" => rules = Rules::load(rest.next().ok_or("--rules needs a file")?)?,
+            "--hard" => hard = true,
+            "--json" => json = true,
+            "--answer" => answer = Some(rest.next().ok_or("--answer needs a word")?),
+            _ => path = Some(arg),
+        }
+    }
+    // the flag overrides the rules file
+    rules.hard |= hard;
+    let answers = rules.read_words(&args[1])?;
+    let words = rules.read_words(&args[2])?;
+    let answer = answer
+        .map(|s| rules.word_from_str(s.as_bytes()).ok_or("invalid answer"))
+        .transpose()?;
+    let text = match path {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => read_to_string(stdin())?,
+    };
+
+    let history = parse_transcript(&text, answer, &rules)?;
+    let report = analyze::<L>(&history, &words, &answers, &rules);
+    if json {
+        println!("{}", report.to_json());
+    } else {
+        println!("{report}");
+    }
+    Ok(())
+}
@@ -0,0 +1,55 @@
+#![feature(portable_simd)]
+
+use core::str;
+
+use wordle::{
+    daily::{answer, format_date, parse_puzzle, puzzle_date, puzzle_number, today, unused},
+    opener::rank_words,
+    read_words,
+    rules::Rules,
+    str_from_word,
+};
+
+const L: usize = 8;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let reveal = std::env::args().any(|a| a == "--reveal");
+    let args = std::env::args()
+        .filter(|a| a != "--reveal")
+        .collect::<Vec<_>>();
+    if args.len() < 2 {
+        println!("daily: usage: daily <answers> [date | #number] [words] [--reveal]");
+        println!("dates are written YYYY-MM-DD; the default is today");
+        return Ok(());
+    }
+    let answers = read_words(&args[1])?;
+    let number = match args.get(2) {
+        Some(s) => parse_puzzle(s).ok_or("expected a date YYYY-MM-DD or #number")?,
+        None => puzzle_number(today()).ok_or("the clock is before the first puzzle")?,
+    };
+
+    println!("puzzle #{number}, {}", format_date(puzzle_date(number)));
+    let Some(soln) = answer(&answers, number) else {
+        return Err("the answer list ends before this puzzle".into());
+    };
+    if reveal {
+        println!("answer: {}", str::from_utf8(&str_from_word(soln)).unwrap());
+    }
+
+    let candidates = unused(&answers, number);
+    println!("{} answers not used before this puzzle", candidates.len());
+    if let Some(path) = args.get(3) {
+        let words = read_words(path)?;
+        println!("best openers against them:");
+        for (w, entropy_left) in rank_words::<L>(&words, candidates, &Rules::default())
+            .into_iter()
+            .take(5)
+        {
+            println!(
+                "{}: {entropy_left}",
+                str::from_utf8(&str_from_word(w)).unwrap()
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use core::str;
+use std::thread::available_parallelism;
+
+use wordle::{
+    disjoint::{max_coverage, search},
+    read_words, str_from_word,
+};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cover = std::env::args().any(|a| a == "--cover");
+    let args = std::env::args()
+        .filter(|a| a != "--cover")
+        .collect::<Vec<_>>();
+    if args.len() < 2 {
+        println!("disjoint: usage: disjoint <words> [k] [--cover]");
+        println!("finds every k words with no letter in common; with --cover, falls back to the sets covering the most letters");
+        return Ok(());
+    }
+    let words = read_words(&args[1])?;
+    let k = args.get(2).map_or(Ok(5), |s| s.parse())?;
+
+    let n_threads = available_parallelism().unwrap().get();
+    let (letters, sets) = if cover {
+        max_coverage(&words, k, n_threads)
+    } else {
+        (5 * k as u32, search(&words, k, 5 * k as u32, n_threads))
+    };
+    println!(
+        "{} sets of {k} words covering {letters} letters",
+        sets.len()
+    );
+    for set in &sets {
+        // anagrams share a slot, separated by slashes
+        println!(
+            "{}",
+            set.iter()
+                .map(|group| group
+                    .iter()
+                    .map(|&w| str::from_utf8(&str_from_word(w)).unwrap().to_owned())
+                    .collect::<Vec<_>>()
+                    .join("/"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+    }
+    Ok(())
+}
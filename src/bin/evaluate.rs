@@ -0,0 +1,81 @@
+#![feature(portable_simd)]
+
+use std::{fs, thread::available_parallelism};
+
+use wordle::{
+    opener::{evaluate, rank},
+    rules::Rules,
+    Word,
+};
+
+const L: usize = 8;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = std::env::args().collect::<Vec<_>>();
+    if args.len() < 4 {
+        println!(
+            "evaluate: usage: evaluate <answers> <words> [--rank] [--rules file] [--file <openers>] [word,word,...]..."
+        );
+        return Ok(());
+    }
+    let mut with_rank = false;
+    let mut rules = Rules::default();
+    let mut lines = Vec::new();
+    let mut rest = args[3..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--rank" => with_rank = true,
+            "--rules" => rules = Rules::load(rest.next().ok_or("--rules needs a file")?)?,
+            "--file" => {
+                let path = rest.next().ok_or("--file needs a path")?;
+                for line in fs::read_to_string(path)?.lines() {
+                    if !line.trim().is_empty() {
+                        lines.push(line.to_owned());
+                    }
+                }
+            }
+            s => lines.push(s.to_owned()),
+        }
+    }
+    let answers = rules.read_words(&args[1])?;
+    let words = rules.read_words(&args[2])?;
+    let openers = lines
+        .iter()
+        .map(|line| parse_opener(line, &rules))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let n_threads = available_parallelism().unwrap().get();
+    for (line, opener) in openers {
+        let eval = evaluate::<L>(&opener, &answers, &rules);
+        print!(
+            "{line}: entropy {}, expected candidates {}, worst bucket {}, solved {}",
+            eval.entropy, eval.expected_candidates, eval.worst_bucket, eval.solved
+        );
+        if with_rank {
+            print!(
+                ", rank {}",
+                rank::<L>(&opener, &words, &answers, n_threads, &rules)
+            );
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+/// Parse a comma- or space-separated list of words, keeping a normalized copy for printing.
+fn parse_opener(s: &str, rules: &Rules) -> Result<(String, Vec<Word>), Box<dyn std::error::Error>> {
+    let parts = s
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>();
+    let opener = parts
+        .iter()
+        .map(|p| {
+            rules
+                .word_from_str(p.as_bytes())
+                .ok_or(format!("invalid word {p:?}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((parts.join(", "), opener))
+}
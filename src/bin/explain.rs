@@ -0,0 +1,27 @@
+use wordle::{explain::explain, grade_from_str, word_from_str};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = std::env::args().collect::<Vec<_>>();
+    if args.len() < 4 || args.len() % 2 != 0 {
+        println!("explain: usage: explain <word> [guess grade]...");
+        println!("tells which guess rules out the word, and why");
+        return Ok(());
+    }
+    let word = word_from_str(args[1].as_bytes()).ok_or("invalid word")?;
+    let history = args[2..]
+        .chunks(2)
+        .map(|pair| {
+            let guess = word_from_str(pair[0].as_bytes())
+                .ok_or_else(|| format!("invalid guess {:?}", pair[0]))?;
+            let graded = grade_from_str(pair[1].as_bytes())
+                .ok_or_else(|| format!("invalid grade {:?}", pair[1]))?;
+            Ok((guess, graded))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    match explain(&history, word) {
+        Some(contradiction) => println!("{}: ruled out by {contradiction}", args[1]),
+        None => println!("{} fits every guess", args[1]),
+    }
+    Ok(())
+}
@@ -0,0 +1,53 @@
+#![feature(portable_simd)]
+
+use std::thread::available_parallelism;
+
+use wordle::{followup::table, metric::Metric, rules::Rules};
+
+const L: usize = 8;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let hard_mode = std::env::args().any(|a| a == "--hard");
+    let mut args = std::env::args()
+        .filter(|a| a != "--hard")
+        .collect::<Vec<_>>();
+    let mut rules = Rules::default();
+    if let Some(i) = args.iter().position(|a| a == "--rules") {
+        rules = Rules::load(args.get(i + 1).ok_or("--rules needs a file")?)?;
+        args.drain(i..i + 2);
+    }
+    if args.len() < 4 {
+        println!(
+            "followup: usage: followup <answers> <words> <opener> [entropy|expected|worst] [--rules file] [--hard]"
+        );
+        return Ok(());
+    }
+    rules.hard |= hard_mode;
+    let answers = rules.read_words(&args[1])?;
+    let words = rules.read_words(&args[2])?;
+    let first = rules
+        .word_from_str(args[3].as_bytes())
+        .ok_or("invalid opener")?;
+    let metric = args.get(4).map_or(Ok(Metric::Entropy), |s| s.parse())?;
+
+    let rows = table::<L>(
+        first,
+        &words,
+        &answers,
+        metric,
+        &rules,
+        available_parallelism().unwrap().get(),
+    );
+    println!("grade\tsize\tbest\tscore");
+    for r in rows {
+        println!(
+            "{}\t{}\t{}\t{}",
+            rules.str_from_grade(r.grade),
+            r.n_candidates,
+            rules.str_from_word(r.best),
+            r.score
+        );
+    }
+
+    Ok(())
+}
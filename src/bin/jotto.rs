@@ -0,0 +1,68 @@
+#![feature(portable_simd)]
+
+use core::str;
+use std::io::{stdin, BufRead};
+
+use wordle::{
+    jotto::Jotto,
+    read_words,
+    scoring::{best_guess, filter},
+    str_from_word, word_from_str,
+};
+
+const L: usize = 8;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = std::env::args().collect::<Vec<_>>();
+    if args.len() < 3 {
+        println!("jotto: usage: jotto <answers> <words>");
+        println!("enter each guess followed by the number of letters it shares, e.g. `trace 2`");
+        return Ok(());
+    }
+    let mut candidates = read_words(&args[1])?;
+    let words = read_words(&args[2])?;
+
+    let mut lines = stdin().lock().lines();
+    loop {
+        match *candidates.as_slice() {
+            [] => {
+                println!("no answer fits every count");
+                break;
+            }
+            [answer] => {
+                println!(
+                    "the answer is {}",
+                    str::from_utf8(&str_from_word(answer)).unwrap()
+                );
+                break;
+            }
+            _ => {}
+        }
+        println!(
+            "{} left; try {}",
+            candidates.len(),
+            str::from_utf8(&str_from_word(
+                best_guess::<L>(&Jotto, &words, &candidates).unwrap()
+            ))
+            .unwrap()
+        );
+
+        let Some(line) = lines.next() else {
+            break;
+        };
+        let line = line?;
+        let mut parts = line.split_whitespace();
+        let guess = parts.next().and_then(|s| word_from_str(s.as_bytes()));
+        let shared = parts
+            .next()
+            .and_then(|s| s.parse().ok())
+            .filter(|&n| n <= 5);
+        let (Some(guess), Some(shared)) = (guess, shared) else {
+            println!("expected a guess and a count from 0 to 5");
+            continue;
+        };
+        candidates = filter::<L>(&Jotto, guess, shared, &candidates);
+    }
+
+    Ok(())
+}
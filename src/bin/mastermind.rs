@@ -0,0 +1,77 @@
+#![feature(portable_simd)]
+
+use std::io::{stdin, BufRead};
+
+use wordle::{
+    mastermind::{Feedback, Mastermind},
+    scoring::{best_guess, filter, Scorer},
+};
+
+const L: usize = 8;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let flags = std::env::args()
+        .filter(|a| a.starts_with("--"))
+        .collect::<Vec<_>>();
+    let args = std::env::args()
+        .filter(|a| !a.starts_with("--"))
+        .collect::<Vec<_>>();
+    if args.len() < 3 {
+        println!(
+            "mastermind: usage: mastermind <length> <n_symbols> [--pegs] [--distinct] [secret]"
+        );
+        println!("without a secret, enter the feedback for each guess as colors (`gybb`) or as black and white peg counts (`1 2`)");
+        return Ok(());
+    }
+    let feedback = if flags.iter().any(|f| f == "--pegs") {
+        Feedback::Pegs
+    } else {
+        Feedback::Colors
+    };
+    let game = Mastermind::new(args[1].parse()?, args[2].parse()?, feedback)
+        .ok_or("need a length from 1 to 6 and from 1 to 32 symbols")?;
+    let codes = game.all_codes(flags.iter().any(|f| f == "--distinct"));
+    let secret = args
+        .get(3)
+        .map(|s| game.code_from_str(s.as_bytes()).ok_or("invalid secret"))
+        .transpose()?;
+
+    let mut candidates = codes.clone();
+    let mut lines = stdin().lock().lines();
+    for turn in 1.. {
+        let guess = match *candidates.as_slice() {
+            [] => {
+                println!("no code fits every feedback");
+                break;
+            }
+            [code] => code,
+            _ => best_guess::<L>(&game, &codes, &candidates).unwrap(),
+        };
+        println!(
+            "{} left; guess {}",
+            candidates.len(),
+            game.str_from_code(guess)
+        );
+
+        let fb = match secret {
+            Some(secret) => game.score(guess, secret),
+            None => {
+                let Some(line) = lines.next() else {
+                    break;
+                };
+                let Some(fb) = game.feedback_from_str(&line?) else {
+                    println!("invalid feedback");
+                    continue;
+                };
+                fb
+            }
+        };
+        if fb == game.score(guess, guess) {
+            println!("solved in {turn} guesses");
+            break;
+        }
+        candidates = filter::<L>(&game, guess, fb, &candidates);
+    }
+
+    Ok(())
+}
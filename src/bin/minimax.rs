@@ -0,0 +1,101 @@
+#![feature(portable_simd)]
+
+use wordle::{
+    minimax::{Minimax, Tree},
+    rules::Rules,
+};
+
+const L: usize = 8;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = std::env::args().collect::<Vec<_>>();
+    if args.len() < 3 {
+        println!("minimax: usage: minimax <answers> <words> [--rules file] [--hard] [--width n] [--first word] [--max n] [--tree]");
+        println!("finds a strategy with the fewest guesses in the worst case; --width all proves its bounds");
+        return Ok(());
+    }
+    let mut rules = Rules::default();
+    let mut hard = false;
+    let mut width = 10;
+    let mut first = None;
+    let mut max_depth = None;
+    let mut show_tree = false;
+    let mut rest = args[3..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--rules" => rules = Rules::load(rest.next().ok_or("--rules needs a file")?)?,
+            "--hard" => hard = true,
+            "--tree" => show_tree = true,
+            "--width" => {
+                width = match rest.next().ok_or("--width needs a value")?.as_str() {
+                    "all" => usize::MAX,
+                    n => n.parse()?,
+                }
+            }
+            "--first" => first = Some(rest.next().ok_or("--first needs a word")?),
+            "--max" => max_depth = Some(rest.next().ok_or("--max needs a value")?.parse()?),
+            _ => return Err(format!("unknown argument {arg:?}").into()),
+        }
+    }
+    // flags override the rules file
+    rules.hard |= hard;
+    let max_depth = max_depth.unwrap_or(rules.max_guesses);
+    let answers = rules.read_words(&args[1])?;
+    let words = rules.read_words(&args[2])?;
+    let first = first
+        .map(|s| {
+            rules
+                .word_from_str(s.as_bytes())
+                .ok_or("invalid first guess")
+        })
+        .transpose()?;
+
+    let mut solver = Minimax::new(&words, width, rules);
+    let solution = match first {
+        Some(first) => solver.solve_from::<L>(first, &answers, max_depth),
+        None => solver.solve::<L>(&answers, max_depth),
+    };
+    let solution = match solution {
+        Ok(solution) => solution,
+        Err(bound) => {
+            println!("no strategy found within {max_depth} guesses; at least {bound} are needed");
+            return Ok(());
+        }
+    };
+
+    let show = |w| rules.str_from_word(w);
+    println!(
+        "always wins within {} guesses, opening with {}; at least {} are needed",
+        solution.depth,
+        show(solution.tree.guess),
+        solution.lower_bound
+    );
+    println!(
+        "{} answers take {} guesses: {}",
+        solution.worst.len(),
+        solution.depth,
+        solution
+            .worst
+            .iter()
+            .map(|&w| show(w))
+            .collect::<Vec<_>>()
+            .join(" ")
+    );
+    if show_tree {
+        print_tree(&solution.tree, &rules, 0);
+    }
+    Ok(())
+}
+
+fn print_tree(tree: &Tree, rules: &Rules, indent: usize) {
+    for (graded, subtree) in &tree.branches {
+        println!(
+            "{:indent$}{} {}: {}",
+            "",
+            rules.str_from_word(tree.guess),
+            rules.str_from_grade(*graded),
+            rules.str_from_word(subtree.guess),
+        );
+        print_tree(subtree, rules, indent + 2);
+    }
+}
@@ -0,0 +1,96 @@
+#![feature(portable_simd)]
+
+use core::str;
+use std::io::{stdin, BufRead};
+
+use wordle::{
+    grade_from_str, multi::Game, read_words, squeeze::grade, str_from_word, word_from_str,
+    ALL_GREEN,
+};
+
+const L: usize = 8;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = std::env::args().collect::<Vec<_>>();
+    if args.len() < 4 {
+        println!("multi: usage: multi <answers> <words> <n_boards> [hidden answer]...");
+        println!("without hidden answers, enter each guess followed by its grade on every unsolved board, e.g. `trace bybbg gbbbb`");
+        return Ok(());
+    }
+    let answers = read_words(&args[1])?;
+    let words = read_words(&args[2])?;
+    let n_boards = args[3].parse()?;
+    let hidden = args[4..]
+        .iter()
+        .map(|s| word_from_str(s.as_bytes()).ok_or("invalid hidden answer"))
+        .collect::<Result<Vec<_>, _>>()?;
+    if !hidden.is_empty() && hidden.len() != n_boards {
+        return Err("need one hidden answer per board".into());
+    }
+    // otherwise its board could never be solved
+    if hidden.iter().any(|h| !answers.contains(h)) {
+        return Err("hidden answers must be in the answer list".into());
+    }
+
+    let mut game = Game::new(n_boards, &answers);
+    let mut lines = stdin().lock().lines();
+    while !game.is_won() {
+        let remaining = game
+            .boards()
+            .iter()
+            .map(|b| if b.solved { 0 } else { b.candidates.len() })
+            .collect::<Vec<_>>();
+        let suggestion = game
+            .suggest::<L>(&words)
+            .ok_or("no answer fits the grades on some board")?;
+        println!(
+            "{remaining:?} left; try {}",
+            str::from_utf8(&str_from_word(suggestion)).unwrap()
+        );
+
+        let (guess, grades) = if hidden.is_empty() {
+            let Some(line) = lines.next() else {
+                break;
+            };
+            let line = line?;
+            let mut parts = line.split_whitespace();
+            let Some(guess) = parts.next().and_then(|s| word_from_str(s.as_bytes())) else {
+                println!("invalid guess");
+                continue;
+            };
+            let given = parts
+                .map(|s| grade_from_str(s.as_bytes()))
+                .collect::<Option<Vec<_>>>();
+            // solved boards keep their all-green grade
+            let unsolved = game.boards().iter().filter(|b| !b.solved).count();
+            let Some(given) = given.filter(|g| g.len() == unsolved) else {
+                println!("expected {unsolved} grades");
+                continue;
+            };
+            let mut given = given.into_iter();
+            let grades = game
+                .boards()
+                .iter()
+                .map(|b| {
+                    if b.solved {
+                        ALL_GREEN
+                    } else {
+                        given.next().unwrap()
+                    }
+                })
+                .collect::<Vec<_>>();
+            (guess, grades)
+        } else {
+            (
+                suggestion,
+                hidden.iter().map(|&h| grade(suggestion, h)).collect(),
+            )
+        };
+        game.update::<L>(guess, &grades);
+    }
+
+    if game.is_won() {
+        println!("solved in {} guesses", game.guesses().len());
+    }
+    Ok(())
+}
@@ -0,0 +1,65 @@
+#![feature(portable_simd)]
+
+use core::str;
+use std::io::{stdin, BufRead};
+
+use wordle::{
+    peaks::{grade_from_str, Peaks},
+    read_words,
+    scoring::{best_guess, filter},
+    str_from_word, word_from_str,
+};
+
+const L: usize = 8;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = std::env::args().collect::<Vec<_>>();
+    if args.len() < 3 {
+        println!("peaks: usage: peaks <answers> <words>");
+        println!("enter each guess followed by its feedback as `=` (same), `<` (earlier) or `>` (later), e.g. `trace <>=<=`");
+        return Ok(());
+    }
+    let mut candidates = read_words(&args[1])?;
+    let words = read_words(&args[2])?;
+
+    let mut lines = stdin().lock().lines();
+    loop {
+        match *candidates.as_slice() {
+            [] => {
+                println!("no answer fits every feedback");
+                break;
+            }
+            [answer] => {
+                println!(
+                    "the answer is {}",
+                    str::from_utf8(&str_from_word(answer)).unwrap()
+                );
+                break;
+            }
+            _ => {}
+        }
+        println!(
+            "{} left; try {}",
+            candidates.len(),
+            str::from_utf8(&str_from_word(
+                best_guess::<L>(&Peaks, &words, &candidates).unwrap()
+            ))
+            .unwrap()
+        );
+
+        let Some(line) = lines.next() else {
+            break;
+        };
+        let line = line?;
+        let mut parts = line.split_whitespace();
+        let guess = parts.next().and_then(|s| word_from_str(s.as_bytes()));
+        let graded = parts.next().and_then(|s| grade_from_str(s.as_bytes()));
+        let (Some(guess), Some(graded)) = (guess, graded) else {
+            println!("expected a guess and its feedback");
+            continue;
+        };
+        candidates = filter::<L>(&Peaks, guess, graded.into(), &candidates);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+#![feature(portable_simd)]
+
+use std::io::{stdin, BufRead};
+
+use wordle::{
+    analysis::analyze,
+    daily::{self, parse_puzzle, puzzle_number, today, unused},
+    game::{render_keyboard, render_row, Game},
+    metric::Metric,
+    rng::Rng,
+    rules::Rules,
+    stats::Stats,
+    tournament::{self, Greedy},
+};
+
+const L: usize = 8;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = std::env::args().collect::<Vec<_>>();
+    if args.len() < 3 {
+        println!("play: usage: play <answers> <words> [--rules file] [--hard] [--max n] [--seed n | --puzzle date] [--analysis] [--stats file]");
+        return Ok(());
+    }
+    let mut rules = Rules::default();
+    let mut hard = false;
+    let mut max_guesses = None;
+    let mut seed = None;
+    let mut puzzle = None;
+    let mut analysis = false;
+    let mut stats_path = None;
+    let mut rest = args[3..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--hard" => hard = true,
+            "--analysis" => analysis = true,
+            "--stats" => stats_path = Some(rest.next().ok_or("--stats needs a file")?),
+            "--max" => max_guesses = Some(rest.next().ok_or("--max needs a value")?.parse()?),
+            "--rules" => rules = Rules::load(rest.next().ok_or("--rules needs a file")?)?,
+            "--seed" => seed = Some(rest.next().ok_or("--seed needs a value")?.parse()?),
+            "--puzzle" => {
+                let s = rest.next().ok_or("--puzzle needs a date or #number")?;
+                puzzle = Some(parse_puzzle(s).ok_or("expected a date YYYY-MM-DD or #number")?);
+            }
+            _ => return Err(format!("unknown argument {arg:?}").into()),
+        }
+    }
+    if seed.is_some() && puzzle.is_some() {
+        return Err("pass either --seed or --puzzle, not both".into());
+    }
+    // flags override the rules file
+    rules.hard |= hard;
+    if let Some(max_guesses) = max_guesses {
+        rules.max_guesses = max_guesses;
+    }
+    let max_guesses = rules.max_guesses;
+    let answers = rules.read_words(&args[1])?;
+    let words = rules.read_words(&args[2])?;
+    if answers.is_empty() {
+        return Err("no answers to pick from".into());
+    }
+
+    // a daily puzzle's answer can't be any of the ones used before it
+    let (id, answer, candidates) = match seed {
+        Some(seed) => (
+            format!("seed:{seed}"),
+            answers[Rng::new(seed).below(answers.len())],
+            &answers[..],
+        ),
+        None => {
+            let number = match puzzle {
+                Some(number) => number,
+                None => puzzle_number(today()).ok_or("the clock is before the first puzzle")?,
+            };
+            let answer = daily::answer(&answers, number)
+                .ok_or("the answer list ends before this puzzle; pick one with --seed")?;
+            println!("puzzle #{number}");
+            (format!("#{number}"), answer, unused(&answers, number))
+        }
+    };
+    let mut stats = match stats_path {
+        Some(path) => Stats::load(path)?,
+        None => Stats::default(),
+    };
+    if stats.record(&id).is_some() {
+        return Err(
+            format!("{id} is already in the stats; pick another with --seed or --puzzle").into(),
+        );
+    }
+    let mut game = match stats.saved(&id) {
+        Some(saved) if saved.answer == answer && saved.rules == rules => {
+            println!("resuming {id}");
+            let game = saved.resume()?;
+            for &(guess, graded) in game.history() {
+                println!("{}", render_row(guess, graded, rules.word_length));
+            }
+            game
+        }
+        _ => Game::with_rules(answer, rules)?,
+    };
+
+    let mut lines = stdin().lock().lines();
+    while !game.is_over() {
+        print!("{}", render_keyboard(&game.keyboard()));
+        println!("guess {} of {max_guesses}:", game.history().len() + 1);
+        let Some(line) = lines.next() else {
+            break;
+        };
+        let line = line?;
+        let Some(guess) = rules.word_from_str(line.trim().to_ascii_lowercase().as_bytes()) else {
+            println!("guesses are {} letters", rules.word_length);
+            continue;
+        };
+        match game.guess(guess, &words) {
+            Ok(_) => {
+                for &(guess, graded) in game.history() {
+                    println!("{}", render_row(guess, graded, rules.word_length));
+                }
+                if let Some(path) = stats_path {
+                    stats.save_game(&id, &game);
+                    stats.save(path)?;
+                }
+            }
+            Err(e) => println!("{e}"),
+        }
+    }
+
+    if !game.is_over() {
+        if stats_path.is_some() {
+            println!("saved {id} to carry on later");
+        }
+        return Ok(());
+    }
+    let answer = rules.str_from_word(game.answer());
+    if game.is_won() {
+        println!("solved in {}/{max_guesses}", game.history().len());
+    } else {
+        println!("the answer was {answer}");
+    }
+    if let Some(path) = stats_path {
+        let bot = Greedy {
+            words: &words,
+            metric: Metric::Entropy,
+            opener: None,
+            rules,
+            candidates_only: false,
+        };
+        let bot_guesses = tournament::play::<L>(&bot, game.answer(), candidates, &rules)?;
+        stats.finish(&id, &game, bot_guesses)?;
+        stats.save(path)?;
+        match bot_guesses {
+            Some(n) => println!("the bot took {n}"),
+            None => println!("the bot didn't solve it either"),
+        }
+        print!("{}", stats.summary());
+    }
+    if analysis {
+        println!(
+            "{}",
+            analyze::<L>(game.history(), &words, candidates, &rules)
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,46 @@
+#![feature(portable_simd)]
+
+use core::str;
+
+use wordle::{
+    grade_from_str, preimage::solve_grid, read_words, str_from_grade, str_from_word, word_from_str,
+};
+
+const L: usize = 8;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let hard = std::env::args().any(|a| a == "--hard");
+    let args = std::env::args()
+        .filter(|a| a != "--hard")
+        .collect::<Vec<_>>();
+    if args.len() < 4 {
+        println!("preimage: usage: preimage <words> <answer> <grade>... [--hard]");
+        println!("lists the guesses giving each grade, e.g. `bygbb`, one grid row at a time");
+        return Ok(());
+    }
+    let words = read_words(&args[1])?;
+    let soln = word_from_str(args[2].as_bytes()).ok_or("invalid answer")?;
+    let rows = args[3..]
+        .iter()
+        .map(|s| grade_from_str(s.as_bytes()).ok_or_else(|| format!("invalid grade {s:?}")))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (row, found) in rows.iter().zip(solve_grid::<L>(&rows, soln, &words, hard)) {
+        println!(
+            "{}: {} words",
+            str::from_utf8(&str_from_grade(*row)).unwrap(),
+            found.len()
+        );
+        for chunk in found.chunks(12) {
+            println!(
+                "  {}",
+                chunk
+                    .iter()
+                    .map(|&w| str::from_utf8(&str_from_word(w)).unwrap().to_owned())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            );
+        }
+    }
+    Ok(())
+}
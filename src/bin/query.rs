@@ -0,0 +1,25 @@
+#![feature(portable_simd)]
+
+use core::str;
+
+use wordle::{query::Query, read_words, str_from_word};
+
+const L: usize = 8;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = std::env::args().collect::<Vec<_>>();
+    if args.len() < 3 {
+        println!("query: usage: query <words> <term>...");
+        println!("terms: a pattern like `c?a?e`, `+r` (contains), `-st` (excludes), `e>=2`, `e<=1`, `e=1`, `r!2` (not at position 2)");
+        return Ok(());
+    }
+    let words = read_words(&args[1])?;
+    let query = args[2..].join(" ").parse::<Query>()?;
+
+    let found = query.filter::<L>(&words);
+    println!("{} words", found.len());
+    for w in found {
+        println!("{}", str::from_utf8(&str_from_word(w)).unwrap());
+    }
+    Ok(())
+}
@@ -0,0 +1,55 @@
+#![feature(portable_simd)]
+
+use core::str;
+use std::io::{read_to_string, stdin};
+
+use wordle::{
+    daily::unused,
+    read_words,
+    share::{infer, parse_all},
+    str_from_word,
+};
+
+const L: usize = 8;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = std::env::args().collect::<Vec<_>>();
+    if args.len() < 3 {
+        println!("share: usage: share <answers> <words> [file]...");
+        println!(
+            "reads shared results, e.g. `Wordle 1,000 4/6*` and its grid, from the files or stdin"
+        );
+        return Ok(());
+    }
+    let answers = read_words(&args[1])?;
+    let words = read_words(&args[2])?;
+    let mut text = String::new();
+    if args.len() == 3 {
+        text = read_to_string(stdin())?;
+    }
+    for path in &args[3..] {
+        text.push_str(&std::fs::read_to_string(path)?);
+        text.push('\n');
+    }
+    let shares = parse_all(&text)?;
+    let Some(number) = shares.first().map(|s| s.number) else {
+        return Err("no shares found".into());
+    };
+    if shares.iter().any(|s| s.number != number) {
+        return Err("the shares are from different puzzles".into());
+    }
+
+    // answers used by earlier puzzles can't come up again
+    let mut candidates = unused(&answers, number).to_vec();
+    println!("puzzle #{number}: {} unused answers", candidates.len());
+    for (i, share) in shares.iter().enumerate() {
+        candidates = infer::<L>(std::slice::from_ref(share), &words, &candidates);
+        println!("after share {}: {} candidates", i + 1, candidates.len());
+    }
+    if candidates.len() <= 20 {
+        for &c in &candidates {
+            println!("{}", str::from_utf8(&str_from_word(c)).unwrap());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use wordle::stats::Stats;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = std::env::args().collect::<Vec<_>>();
+    if args.len() < 2 {
+        println!("stats: usage: stats <file>");
+        println!("shows the statistics kept by play --stats");
+        return Ok(());
+    }
+    let stats = Stats::load(&args[1])?;
+    print!("{}", stats.summary());
+    for saved in &stats.saved {
+        println!(
+            "{} in progress, {} of {} guesses played",
+            saved.id,
+            saved.guesses.len(),
+            saved.rules.max_guesses
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,151 @@
+#![feature(portable_simd)]
+
+use std::thread::available_parallelism;
+
+use wordle::{
+    metric::Metric,
+    minimax::Minimax,
+    rng::Rng,
+    rules::Rules,
+    tournament::{run, Follow, Greedy, Strategy},
+};
+
+const L: usize = 8;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = std::env::args().collect::<Vec<_>>();
+    if args.len() < 3 {
+        println!("tournament: usage: tournament <answers> <words> [--rules file] [--strategies s,s,...] [--opener word] [--max n] [--subsets size] [--rounds n] [--seed n] [--diff]");
+        println!("strategies: entropy, expected or worst, each optionally with /candidates and /hard, or minimax");
+        return Ok(());
+    }
+    let mut names = vec![
+        "entropy".to_owned(),
+        "worst".to_owned(),
+        "entropy/candidates".to_owned(),
+    ];
+    let mut opener = None;
+    let mut rules = Rules::default();
+    let mut max_guesses = None;
+    let mut subset_size = 100;
+    let mut rounds = 1000;
+    let mut seed = 0;
+    let mut show_diff = false;
+    let mut rest = args[3..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--strategies" => {
+                let list = rest.next().ok_or("--strategies needs a list")?;
+                names = list.split(',').map(str::to_owned).collect();
+            }
+            "--opener" => opener = Some(rest.next().ok_or("--opener needs a word")?),
+            "--rules" => rules = Rules::load(rest.next().ok_or("--rules needs a file")?)?,
+            "--max" => max_guesses = Some(rest.next().ok_or("--max needs a value")?.parse()?),
+            "--subsets" => subset_size = rest.next().ok_or("--subsets needs a value")?.parse()?,
+            "--rounds" => rounds = rest.next().ok_or("--rounds needs a value")?.parse()?,
+            "--seed" => seed = rest.next().ok_or("--seed needs a value")?.parse()?,
+            "--diff" => show_diff = true,
+            _ => return Err(format!("unknown argument {arg:?}").into()),
+        }
+    }
+
+    if let Some(max_guesses) = max_guesses {
+        rules.max_guesses = max_guesses;
+    }
+    let max_guesses = rules.max_guesses;
+    let answers = rules.read_words(&args[1])?;
+    let words = rules.read_words(&args[2])?;
+    let opener = opener
+        .map(|s| rules.word_from_str(s.as_bytes()).ok_or("invalid opener"))
+        .transpose()?;
+
+    let mut strategies: Vec<Box<dyn Strategy<L> + '_>> = Vec::new();
+    for name in &names {
+        if name == "minimax" {
+            let mut solver = Minimax::new(&words, 5, rules);
+            let solution = match opener {
+                Some(first) => solver.solve_from::<L>(first, &answers, max_guesses),
+                None => solver.solve::<L>(&answers, max_guesses),
+            }
+            .map_err(|bound| format!("minimax: no strategy within {max_guesses} guesses; at least {bound} are needed"))?;
+            strategies.push(Box::new(Follow {
+                name: name.clone(),
+                tree: solution.tree,
+            }));
+            continue;
+        }
+        let mut parts = name.split('/');
+        let metric = parts.next().unwrap().parse::<Metric>()?;
+        let mut greedy = Greedy {
+            words: &words,
+            metric,
+            opener,
+            rules,
+            candidates_only: false,
+        };
+        for part in parts {
+            match part {
+                "candidates" => greedy.candidates_only = true,
+                "hard" => greedy.rules.hard = true,
+                _ => return Err(format!("unknown strategy option {part:?} in {name:?}").into()),
+            }
+        }
+        if greedy.opener.is_none() {
+            // the same for every answer, so only worked out once
+            greedy.opener = Some(Strategy::<L>::guess(&greedy, &[], &answers));
+        }
+        strategies.push(Box::new(greedy));
+    }
+    let strategies = strategies.iter().map(|s| s.as_ref()).collect::<Vec<_>>();
+
+    let n_threads = available_parallelism().unwrap().get();
+    let results = run::<L>(&strategies, &answers, &rules, n_threads)?;
+
+    if show_diff {
+        println!("answer: {}", results.names.join(" "));
+        for (answer, row) in results.differences() {
+            let row = row
+                .iter()
+                .map(|g| g.map_or("x".to_owned(), |g| g.to_string()))
+                .collect::<Vec<_>>();
+            println!("{}: {}", rules.str_from_word(answer), row.join(" "));
+        }
+        println!();
+    }
+
+    println!("leaderboard:");
+    for (place, standing) in results.leaderboard().iter().enumerate() {
+        println!(
+            "{}. {}: {standing}",
+            place + 1,
+            results.names[standing.strategy]
+        );
+    }
+
+    println!();
+    println!("head to head (wins-losses-ties, sign test p, 95% interval for the difference in mean guesses):");
+    let mut rng = Rng::new(seed);
+    for a in 0..names.len() {
+        for b in a + 1..names.len() {
+            let h = results.head_to_head(a, b);
+            let (lo, hi) = results.bootstrap(a, b, rounds, &mut rng);
+            println!(
+                "{} vs {}: {}-{}-{}, p {:.3e}, [{lo:+.4}, {hi:+.4}]",
+                results.names[a],
+                results.names[b],
+                h.wins,
+                h.losses,
+                h.ties,
+                h.p_value()
+            );
+        }
+    }
+
+    println!();
+    println!("best on random subsets of {subset_size} answers:");
+    let wins = results.subset_wins(subset_size, rounds, &mut rng);
+    for (name, w) in results.names.iter().zip(wins) {
+        println!("{name}: {:.1}%", 100.0 * w);
+    }
+    Ok(())
+}
@@ -0,0 +1,58 @@
+#![feature(portable_simd)]
+
+use wordle::{rules::Rules, winprob::WinProb};
+
+const L: usize = 8;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = std::env::args().collect::<Vec<_>>();
+    if args.len() < 3 {
+        println!("winprob: usage: winprob <answers> <words> [--rules file] [--max n] [--width n] [--exact n] [guess grade]...");
+        println!(
+            "ranks guesses by the chance of solving within the guesses left after those played"
+        );
+        return Ok(());
+    }
+    let mut rules = Rules::default();
+    let mut max_guesses = None;
+    let mut width = 20;
+    let mut exact_below = 20;
+    let mut played = Vec::new();
+    let mut rest = args[3..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--rules" => rules = Rules::load(rest.next().ok_or("--rules needs a file")?)?,
+            "--max" => max_guesses = Some(rest.next().ok_or("--max needs a value")?.parse()?),
+            "--width" => width = rest.next().ok_or("--width needs a value")?.parse()?,
+            "--exact" => exact_below = rest.next().ok_or("--exact needs a value")?.parse()?,
+            guess => {
+                let graded = rest.next().ok_or("expected a grade after each guess")?;
+                played.push((guess, graded));
+            }
+        }
+    }
+    let max_guesses = max_guesses.unwrap_or(rules.max_guesses);
+    let mut candidates = rules.read_words(&args[1])?;
+    let words = rules.read_words(&args[2])?;
+    for &(guess, graded) in &played {
+        let guess = rules
+            .word_from_str(guess.as_bytes())
+            .ok_or("invalid guess")?;
+        let graded = rules
+            .grade_from_str(graded.as_bytes())
+            .ok_or("invalid grade")?;
+        candidates = rules.filter::<L>(guess, graded, &candidates);
+    }
+    let guesses_left = max_guesses - played.len().min(max_guesses);
+
+    let mut solver = WinProb::new(&words, width, exact_below, rules);
+    println!(
+        "{} candidates, {guesses_left} guesses left: {:.4} to win",
+        candidates.len(),
+        solver.prob::<L>(&candidates, guesses_left)
+    );
+    for (guess, p) in solver.rank::<L>(&candidates, guesses_left, 10) {
+        println!("{}: {p:.4}", rules.str_from_word(guess));
+    }
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::Word;
+
+/// Days since 1970-01-01 of the first puzzle, number 0, on 2021-06-19.
+pub const FIRST_DAY: i64 = 18797;
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    // Howard Hinnant's algorithm, with years starting in March so leap days come last
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// The year, month and day of a number of days since 1970-01-01.
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    (year, month, day)
+}
+
+/// Parse a date written `YYYY-MM-DD` into days since 1970-01-01.
+pub fn parse_date(s: &str) -> Option<i64> {
+    let mut parts = s.splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts
+        .next()?
+        .parse()
+        .ok()
+        .filter(|m| (1..=12).contains(m))?;
+    let day = parts.next()?.parse().ok()?;
+    let days = days_from_civil(year, month, day);
+    // rejects days past the end of the month, which roll over into the next
+    (civil_from_days(days) == (year, month, day)).then_some(days)
+}
+
+pub fn format_date(days: i64) -> String {
+    let (year, month, day) = civil_from_days(days);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Today in UTC, as days since 1970-01-01.
+pub fn today() -> i64 {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    (secs / 86400) as i64
+}
+
+/// The number of the puzzle published on a day, or `None` before the first.
+pub fn puzzle_number(days: i64) -> Option<usize> {
+    usize::try_from(days - FIRST_DAY).ok()
+}
+
+pub fn puzzle_date(number: usize) -> i64 {
+    FIRST_DAY + number as i64
+}
+
+/// Parse a puzzle given either as a date, `YYYY-MM-DD`, or as its number, `#n`.
+pub fn parse_puzzle(s: &str) -> Option<usize> {
+    match s.strip_prefix('#') {
+        Some(n) => n.parse().ok(),
+        None => puzzle_number(parse_date(s)?),
+    }
+}
+
+/// The answer to a puzzle, given `answers` in their original daily order.
+pub fn answer(answers: &[Word], number: usize) -> Option<Word> {
+    answers.get(number).copied()
+}
+
+/// The answers which have not been used before puzzle `number`, and so are still candidates.
+pub fn unused(answers: &[Word], number: usize) -> &[Word] {
+    &answers[number.min(answers.len())..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_puzzle() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(parse_date("2021-06-19"), Some(FIRST_DAY));
+        assert_eq!(parse_puzzle("2021-06-19"), Some(0));
+        assert_eq!(parse_puzzle("#0"), Some(0));
+        assert_eq!(parse_puzzle("2021-06-18"), None);
+        assert_eq!(format_date(puzzle_date(1000)), "2024-03-15");
+        assert_eq!(parse_date("2023-02-29"), None);
+        assert_eq!(
+            parse_date("2024-02-29").map(format_date).as_deref(),
+            Some("2024-02-29")
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        for days in -1000..100_000 {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+    }
+}
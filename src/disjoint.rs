@@ -0,0 +1,266 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread::scope,
+};
+
+use crate::Word;
+
+/// The letters of `word` as a 26-bit set, `a` in the lowest bit.
+pub fn letter_mask(word: Word) -> u32 {
+    let mut mask = 0;
+    for i in 0..5 {
+        mask |= 1 << ((word >> (5 * i)) & 0x1f);
+    }
+    mask
+}
+
+/// A set of words, each given as every word in the list with the same letters.
+pub type Cover = Vec<Vec<Word>>;
+
+/// A partly built set: the letters covered, the letters decided against, and the masks chosen.
+#[derive(Clone)]
+struct Node {
+    covered: u32,
+    excluded: u32,
+    chosen: Vec<u32>,
+}
+
+struct Searcher {
+    /// The letters from rarest to commonest.
+    order: [u32; 26],
+    /// For each letter, the masks containing it.
+    containing: [Vec<u32>; 26],
+    /// Every mask, for the words adding no new letters.
+    masks: Vec<u32>,
+    k: usize,
+    min_letters: u32,
+}
+
+impl Searcher {
+    /// The rarest letter not yet decided on.
+    fn next_letter(&self, node: &Node) -> Option<u32> {
+        let decided = node.covered | node.excluded;
+        self.order.into_iter().find(|&c| decided & (1 << c) == 0)
+    }
+
+    /// Whether `node` has no children: either the set is full, or every letter is decided and
+    /// only [`Searcher::fill`] can add to it.
+    fn is_leaf(&self, node: &Node) -> bool {
+        node.chosen.len() == self.k || self.next_letter(node).is_none()
+    }
+
+    /// Every way of taking `node` one step further: either one of the masks covering the next
+    /// letter is chosen, or that letter is left uncovered.
+    fn children(&self, node: &Node) -> Vec<Node> {
+        let mut children = Vec::new();
+        let remaining = (self.k - node.chosen.len()) as u32;
+        let Some(c) = self.next_letter(node) else {
+            return children;
+        };
+        // even if every remaining word brings five new letters
+        if node.covered.count_ones() + 5 * remaining < self.min_letters {
+            return children;
+        }
+        for &mask in &self.containing[c as usize] {
+            if mask & node.excluded != 0 {
+                continue;
+            }
+            let mut chosen = node.chosen.clone();
+            chosen.push(mask);
+            children.push(Node {
+                covered: node.covered | mask,
+                excluded: node.excluded,
+                chosen,
+            });
+        }
+        if 26 - (node.excluded.count_ones() + 1) >= self.min_letters {
+            children.push(Node {
+                covered: node.covered,
+                excluded: node.excluded | (1 << c),
+                chosen: node.chosen.clone(),
+            });
+        }
+        children
+    }
+
+    fn dfs(&self, node: Node, found: &mut HashSet<Vec<u32>>) {
+        if node.chosen.len() == self.k {
+            if node.covered.count_ones() >= self.min_letters {
+                let mut set = node.chosen;
+                set.sort_unstable();
+                found.insert(set);
+            }
+            return;
+        }
+        if self.next_letter(&node).is_none() {
+            let spare = self
+                .masks
+                .iter()
+                .copied()
+                .filter(|&m| m & !node.covered == 0 && !node.chosen.contains(&m))
+                .collect::<Vec<_>>();
+            self.fill(node, &spare, found);
+            return;
+        }
+        for child in self.children(&node) {
+            self.dfs(child, found);
+        }
+    }
+
+    /// Complete `node` with masks from `spare`, which only repeat letters already covered, in
+    /// every way possible. A set with fewer than `k` words adding new letters is only found so.
+    fn fill(&self, node: Node, spare: &[u32], found: &mut HashSet<Vec<u32>>) {
+        if node.chosen.len() == self.k {
+            self.dfs(node, found);
+            return;
+        }
+        for (i, &mask) in spare.iter().enumerate() {
+            let mut chosen = node.chosen.clone();
+            chosen.push(mask);
+            let child = Node {
+                chosen,
+                ..node.clone()
+            };
+            self.fill(child, &spare[i + 1..], found);
+        }
+    }
+}
+
+/// Every set of `k` words from `words` between them using at least `min_letters` different
+/// letters. Words with the same letters are grouped together, so each set is reported once.
+/// With `min_letters` equal to `5 * k`, these are the sets with pairwise disjoint letters.
+pub fn search(words: &[Word], k: usize, min_letters: u32, n_threads: usize) -> Vec<Cover> {
+    let mut groups = BTreeMap::<u32, Vec<Word>>::new();
+    for &w in words {
+        groups.entry(letter_mask(w)).or_default().push(w);
+    }
+    if k == 0 || min_letters > 26 {
+        return Vec::new();
+    }
+
+    // branching on the rarest letters first keeps the tree narrow
+    let mut frequency = [0usize; 26];
+    for &mask in groups.keys() {
+        for (c, f) in frequency.iter_mut().enumerate() {
+            *f += (mask >> c) as usize & 1;
+        }
+    }
+    let mut order: [u32; 26] = std::array::from_fn(|c| c as u32);
+    order.sort_by_key(|&c| frequency[c as usize]);
+    let mut containing: [Vec<u32>; 26] = std::array::from_fn(|_| Vec::new());
+    for &mask in groups.keys() {
+        for (c, masks) in containing.iter_mut().enumerate() {
+            if mask & (1 << c) != 0 {
+                masks.push(mask);
+            }
+        }
+    }
+
+    let found = Mutex::new(HashSet::new());
+    let searcher = Searcher {
+        order,
+        containing,
+        masks: groups.keys().copied().collect(),
+        k,
+        min_letters,
+    };
+
+    // split the top of the tree into enough pieces to keep every thread busy
+    let mut frontier = vec![Node {
+        covered: 0,
+        excluded: 0,
+        chosen: Vec::new(),
+    }];
+    while frontier.len() < 64 * n_threads {
+        let before = frontier.len();
+        frontier = frontier
+            .into_iter()
+            .flat_map(|node| {
+                if searcher.is_leaf(&node) {
+                    vec![node]
+                } else {
+                    searcher.children(&node)
+                }
+            })
+            .collect();
+        if frontier.len() <= before && frontier.iter().all(|n| searcher.is_leaf(n)) {
+            break;
+        }
+    }
+
+    let next_node = AtomicUsize::new(0);
+    scope(|s| {
+        for _ in 0..n_threads {
+            s.spawn(|| {
+                let mut local = HashSet::new();
+                loop {
+                    let i = next_node.fetch_add(1, Ordering::Relaxed);
+                    if i >= frontier.len() {
+                        break;
+                    }
+                    searcher.dfs(frontier[i].clone(), &mut local);
+                }
+                found.lock().unwrap().extend(local);
+            });
+        }
+    });
+
+    let mut sets = found.into_inner().unwrap().into_iter().collect::<Vec<_>>();
+    sets.sort_unstable();
+    sets.into_iter()
+        .map(|set| set.iter().map(|mask| groups[mask].clone()).collect())
+        .collect()
+}
+
+/// The most letters `k` words from `words` can cover between them, and every set which does.
+pub fn max_coverage(words: &[Word], k: usize, n_threads: usize) -> (u32, Vec<Cover>) {
+    let mut target = (5 * k).min(26) as u32;
+    loop {
+        let found = search(words, k, target, n_threads);
+        if !found.is_empty() || target == 0 {
+            return (target, found);
+        }
+        target -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::word_from_str;
+
+    use super::*;
+
+    #[test]
+    fn finds_disjoint_sets() {
+        let words = [
+            "fjord", "gucks", "nymph", "vibex", "waltz", "crate", "trace", "lions",
+        ]
+        .map(|s| word_from_str(s.as_bytes()).unwrap());
+        assert_eq!(letter_mask(words[5]), letter_mask(words[6]));
+
+        let found = search(&words, 5, 25, 2);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].len(), 5);
+
+        // `crate` and `trace` are one anagram group, disjoint from `lions`
+        let pairs = search(&words[5..], 2, 10, 1);
+        assert_eq!(pairs, [vec![vec![words[7]], vec![words[5], words[6]]]]);
+
+        // with no three disjoint words, `cruel` overlaps the other two
+        let cruel = word_from_str(b"cruel").unwrap();
+        let (letters, sets) = max_coverage(&[words[5], words[7], cruel], 3, 1);
+        assert_eq!((letters, sets.len()), (11, 1));
+
+        // only two words can be disjoint, so the third repeats their letters
+        let w = |s: &str| word_from_str(s.as_bytes()).unwrap();
+        let overlapping = [w("abcde"), w("fghij"), w("abcdf")];
+        let (letters, sets) = max_coverage(&overlapping, 3, 1);
+        assert_eq!(letters, 10);
+        assert_eq!((sets.len(), sets[0].len()), (1, 3));
+        assert_eq!(max_coverage(&overlapping, 4, 1), (0, Vec::new()));
+    }
+}
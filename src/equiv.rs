@@ -0,0 +1,169 @@
+use std::{
+    collections::HashMap,
+    simd::{LaneCount, Simd, SupportedLaneCount},
+};
+
+use crate::{rules::Rules, Word};
+
+/// The class of the guess itself, when it is one of the candidates.
+const SOLVED: u16 = 0;
+
+/// How `guess` splits `candidates`, with the classes numbered from 1 in order of first
+/// appearance, so guesses which split the candidates the same way get the same signature
+/// whatever their grades. The candidate equal to `guess` is marked 0.
+pub fn signature<const L: usize>(guess: Word, candidates: &[Word], rules: &Rules) -> Vec<u16>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let mut grades = Vec::with_capacity(candidates.len());
+    let (prefix, simds, suffix) = candidates.as_simd::<L>();
+    grades.extend(prefix.iter().map(|&c| rules.grade(guess, c) as u32));
+    for &c in simds {
+        grades.extend(rules.gradel(Simd::splat(guess), c).to_array());
+    }
+    grades.extend(suffix.iter().map(|&c| rules.grade(guess, c) as u32));
+
+    let mut labels = HashMap::new();
+    grades
+        .into_iter()
+        .map(|g| {
+            if g == rules.all_green() as u32 {
+                return SOLVED;
+            }
+            let next = labels.len() as u16 + 1;
+            *labels.entry(g).or_insert(next)
+        })
+        .collect()
+}
+
+/// Whether every class of the partition `fine` lies inside a class of `coarse`.
+pub fn refines(fine: &[u16], coarse: &[u16]) -> bool {
+    let mut outer = vec![None; fine.iter().copied().max().unwrap_or(0) as usize + 1];
+    fine.iter().zip(coarse).all(|(&f, &c)| {
+        let outer = &mut outer[f as usize];
+        *outer.get_or_insert(c) == c
+    })
+}
+
+fn n_classes(signature: &[u16]) -> usize {
+    let max = signature.iter().copied().max().unwrap_or(0) as usize;
+    max + signature.contains(&SOLVED) as usize
+}
+
+/// The groups with their signatures.
+fn signed_groups<const L: usize>(
+    guesses: &[Word],
+    candidates: &[Word],
+    rules: &Rules,
+) -> Vec<(Vec<u16>, Vec<Word>)>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let mut index = HashMap::<Vec<u16>, usize>::new();
+    let mut groups: Vec<(Vec<u16>, Vec<Word>)> = Vec::new();
+    for &g in guesses {
+        let sig = signature::<L>(g, candidates, rules);
+        match index.get(&sig) {
+            Some(&i) => groups[i].1.push(g),
+            None => {
+                index.insert(sig.clone(), groups.len());
+                groups.push((sig, vec![g]));
+            }
+        }
+    }
+    groups
+}
+
+/// Group `guesses` by signature against `candidates`, keeping the order of first appearance.
+pub fn group<const L: usize>(guesses: &[Word], candidates: &[Word], rules: &Rules) -> Vec<Vec<Word>>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    signed_groups::<L>(guesses, candidates, rules)
+        .into_iter()
+        .map(|(_, g)| g)
+        .collect()
+}
+
+/// The first of `guesses` with each signature against `candidates`, in order.
+pub fn distinct<const L: usize>(guesses: &[Word], candidates: &[Word], rules: &Rules) -> Vec<Word>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    group::<L>(guesses, candidates, rules)
+        .into_iter()
+        .map(|g| g[0])
+        .collect()
+}
+
+/// Like [`distinct`], but also dropping every guess dominated by another: one whose partition
+/// of `candidates` is refined by the other's, unless it could be the answer and the other
+/// can't. No strategy needs a dominated guess, whether it aims for the fewest guesses or the
+/// best chance of winning. Comparing every pair is quadratic, so this suits small sets of
+/// candidates best.
+pub fn undominated<const L: usize>(
+    guesses: &[Word],
+    candidates: &[Word],
+    rules: &Rules,
+) -> Vec<Word>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let sigs = signed_groups::<L>(guesses, candidates, rules)
+        .into_iter()
+        .map(|(sig, g)| (g[0], sig))
+        .collect::<Vec<_>>();
+    let is_candidate = |sig: &[u16]| sig.contains(&SOLVED);
+    let counts = sigs.iter().map(|(_, s)| n_classes(s)).collect::<Vec<_>>();
+    (0..sigs.len())
+        .filter(|&a| {
+            let (_, ref coarse) = sigs[a];
+            !(0..sigs.len()).any(|b| {
+                let (_, ref fine) = sigs[b];
+                b != a
+                    && counts[b] >= counts[a]
+                    && (!is_candidate(coarse) || is_candidate(fine))
+                    && refines(fine, coarse)
+                    // of two guesses refining each other keep the first
+                    && !(b > a && refines(coarse, fine) && is_candidate(coarse) == is_candidate(fine))
+            })
+        })
+        .map(|a| sigs[a].0)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::word_from_str;
+
+    use super::*;
+
+    #[test]
+    fn collapses_and_dominates() {
+        let w = |s: &str| word_from_str(s.as_bytes()).unwrap();
+        let candidates = ["fight", "light", "might", "night", "right", "sight"].map(w);
+        // `flash` and `flask` split the same way; `folky` only finds f and l, a coarsening
+        let guesses = ["flash", "flask", "folky", "zzzzz", "night"].map(w);
+        let rules = Rules::default();
+
+        assert_eq!(
+            signature::<4>(w("flash"), &candidates, &rules),
+            signature::<4>(w("flask"), &candidates, &rules)
+        );
+        assert_eq!(signature::<4>(w("night"), &candidates, &rules)[3], SOLVED);
+        assert_eq!(
+            group::<4>(&guesses, &candidates, &rules),
+            [
+                vec![w("flash"), w("flask")],
+                vec![w("folky")],
+                vec![w("zzzzz")],
+                vec![w("night")]
+            ]
+        );
+        // `night` splits off only itself, but could win straight away
+        assert_eq!(
+            undominated::<4>(&guesses, &candidates, &rules),
+            [w("flash"), w("night")]
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use std::fmt;
+
+use crate::{packed::greens_and_bank, str_from_grade, str_from_word, Grade, Word, BLACK, GREEN};
+
+/// Why a word can't be the answer after some guess.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reason {
+    /// The guess had this letter green here. Positions count from 1.
+    NeedsAt { letter: u8, position: usize },
+    /// The guess had this letter here, but not green.
+    NotAt { letter: u8, position: usize },
+    /// The guess revealed this many of the letter.
+    AtLeast { letter: u8, count: usize },
+    /// The guess had a black copy of the letter, so there are no more than revealed.
+    AtMost { letter: u8, count: usize },
+}
+
+/// The first guess in a history which rules out a word, and why.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Contradiction {
+    /// Which guess, counting from 0.
+    pub index: usize,
+    pub guess: Word,
+    pub grade: Grade,
+    pub reason: Reason,
+}
+
+fn letter(word: Word, i: usize) -> u32 {
+    (word >> (5 * i)) & 0x1f
+}
+
+/// Why `word` couldn't have given `guess` the grade `graded` under the New York Times rule, or
+/// `None` if it could.
+pub fn contradiction(guess: Word, graded: Grade, word: Word) -> Option<Reason> {
+    let color = |i: usize| (graded >> (2 * i)) & 0b11;
+    for i in 0..5 {
+        let c = letter(guess, i);
+        let is_green = color(i) == GREEN;
+        if is_green != (letter(word, i) == c) {
+            let (letter, position) = (c as u8 + b'a', i + 1);
+            return Some(if is_green {
+                Reason::NeedsAt { letter, position }
+            } else {
+                Reason::NotAt { letter, position }
+            });
+        }
+    }
+
+    // the greens agree, so the bank holds the letters of `word` left for yellows
+    let (_, bank) = greens_and_bank(guess, word);
+    for i in 0..5 {
+        let c = letter(guess, i);
+        if (0..i).any(|j| letter(guess, j) == c) {
+            continue;
+        }
+        let (mut greens, mut yellows, mut capped) = (0, 0, false);
+        for j in (0..5).filter(|&j| letter(guess, j) == c) {
+            match color(j) {
+                GREEN => greens += 1,
+                BLACK => capped = true,
+                _ => yellows += 1,
+            }
+        }
+        let left = ((bank >> (3 * c)) & 0b111) as usize;
+        let letter = c as u8 + b'a';
+        let count = greens + yellows;
+        if left < yellows {
+            return Some(Reason::AtLeast { letter, count });
+        }
+        if capped && left > yellows {
+            return Some(Reason::AtMost { letter, count });
+        }
+    }
+    None
+}
+
+/// The first of `history` which `word` contradicts, or `None` if it could still be the answer.
+pub fn explain(history: &[(Word, Grade)], word: Word) -> Option<Contradiction> {
+    history
+        .iter()
+        .enumerate()
+        .find_map(|(index, &(guess, grade))| {
+            contradiction(guess, grade, word).map(|reason| Contradiction {
+                index,
+                guess,
+                grade,
+                reason,
+            })
+        })
+}
+
+fn count_of(count: usize, letter: u8) -> String {
+    let n = ["no", "one", "two", "three", "four", "five"]
+        .get(count)
+        .map_or(count.to_string(), |n| n.to_string());
+    let plural = if count == 1 { "" } else { "'s" };
+    format!("{n} {}{plural}", letter as char)
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Reason::NeedsAt { letter, position } => {
+                write!(f, "needs {} in position {position}", letter as char)
+            }
+            Reason::NotAt { letter, position } => {
+                write!(f, "{} cannot be in position {position}", letter as char)
+            }
+            Reason::AtLeast { letter, count } => {
+                write!(f, "needs at least {}", count_of(count, letter))
+            }
+            Reason::AtMost { letter, count: 0 } => write!(f, "has no {}", letter as char),
+            Reason::AtMost { letter, count } => {
+                write!(f, "has at most {}", count_of(count, letter))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Contradiction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "guess {} ({} {}): {}",
+            self.index + 1,
+            String::from_utf8_lossy(&str_from_word(self.guess)),
+            String::from_utf8_lossy(&str_from_grade(self.grade)),
+            self.reason
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{grade_from_str, packed::grade, word_from_str};
+
+    use super::*;
+
+    #[test]
+    fn explains_eliminations() {
+        let w = |s: &str| word_from_str(s.as_bytes()).unwrap();
+        let history = [w("crate"), w("geese")].map(|g| (g, grade(g, w("theme"))));
+        assert_eq!(
+            history.map(|(_, g)| g),
+            [b"bbbyg", b"bbgbg"].map(|g| grade_from_str(g).unwrap())
+        );
+        let explained = |s: &str| explain(&history, w(s)).unwrap().to_string();
+
+        assert_eq!(explain(&history, w("theme")), None);
+        assert_eq!(
+            explained("butte"),
+            "guess 1 (crate bbbyg): t cannot be in position 4"
+        );
+        assert_eq!(
+            explained("tacit"),
+            "guess 1 (crate bbbyg): needs e in position 5"
+        );
+        assert_eq!(explained("three"), "guess 1 (crate bbbyg): has no r");
+        assert_eq!(
+            explained("tilde"),
+            "guess 2 (geese bbgbg): needs e in position 3"
+        );
+
+        // the green and yellow e's of `geese` need three; the black e of `eerie` caps them at two
+        let geese = grade(w("geese"), w("emcee"));
+        assert_eq!(
+            contradiction(w("geese"), geese, w("three")),
+            Some(Reason::AtLeast {
+                letter: b'e',
+                count: 3
+            })
+        );
+        let eerie = grade(w("eerie"), w("elder"));
+        assert_eq!(
+            contradiction(w("eerie"), eerie, w("emeer"))
+                .unwrap()
+                .to_string(),
+            "has at most two e's"
+        );
+
+        for (guess, answer) in [("geese", "emcee"), ("eerie", "elder"), ("crate", "theme")] {
+            let graded = grade(w(guess), w(answer));
+            for word in [
+                "emcee", "theme", "three", "tilde", "eerie", "emeer", "elder", "geese",
+            ] {
+                assert_eq!(
+                    contradiction(w(guess), graded, w(word)).is_none(),
+                    grade(w(guess), w(word)) == graded
+                );
+            }
+        }
+    }
+}
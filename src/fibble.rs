@@ -0,0 +1,117 @@
+use crate::{packed::grade, Grade, Word, N_GRADES};
+
+/// Number of tiles at which two grades differ.
+pub fn n_altered(a: Grade, b: Grade) -> u32 {
+    (0..5)
+        .filter(|i| (a >> (2 * i)) & 0b11 != (b >> (2 * i)) & 0b11)
+        .count() as u32
+}
+
+/// Every grade which differs from `true_grade` at exactly `k` tiles.
+pub fn lies(true_grade: Grade, k: u32) -> Vec<Grade> {
+    let mut out = Vec::new();
+    push_lies(true_grade, 0, k, &mut out);
+    out
+}
+
+fn push_lies(grade: Grade, from: u32, k: u32, out: &mut Vec<Grade>) {
+    if k == 0 {
+        out.push(grade);
+        return;
+    }
+    for i in from..5 {
+        let shift = 2 * i;
+        let color = (grade >> shift) & 0b11;
+        for other in [0b00, 0b01, 0b10] {
+            if other != color {
+                push_lies(
+                    (grade & !(0b11 << shift)) | (other << shift),
+                    i + 1,
+                    k - 1,
+                    out,
+                );
+            }
+        }
+    }
+}
+
+/// Whether `soln` could have produced `observed` for `guess` with exactly `k` tiles altered.
+pub fn is_consistent(guess: Word, soln: Word, observed: Grade, k: u32) -> bool {
+    n_altered(grade(guess, soln), observed) == k
+}
+
+pub fn filter(guess: Word, observed: Grade, k: u32, solns: &[Word]) -> Vec<Word> {
+    solns
+        .iter()
+        .copied()
+        .filter(|&s| is_consistent(guess, s, observed, k))
+        .collect()
+}
+
+/// Expected entropy left after `word` when exactly `k` tiles of its grade are altered, choosing
+/// which ones uniformly at random.
+///
+/// Unlike honest grades, one answer can produce many observations, so the buckets overlap: the
+/// answers left after seeing a grade are all those within `k` alterations of it.
+pub fn entropy_after(word: Word, solns: &[Word], k: u32) -> f32 {
+    let mut true_count = [0u32; N_GRADES];
+    for &answer in solns {
+        true_count[grade(word, answer) as usize] += 1;
+    }
+
+    let mut observed_count = [0u32; N_GRADES];
+    for (true_grade, &n) in true_count.iter().enumerate() {
+        if n > 0 {
+            for lie in lies(true_grade as Grade, k) {
+                observed_count[lie as usize] += n;
+            }
+        }
+    }
+
+    // every answer makes each of its observations equally likely
+    let n_lies = lies(0, k).len() as f32;
+    observed_count
+        .into_iter()
+        .filter(|&n| n > 1)
+        .map(|n| (n as f32).log2() * n as f32)
+        .sum::<f32>()
+        / (solns.len() as f32 * n_lies)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::word_from_str;
+
+    use super::*;
+
+    #[test]
+    fn one_lie_per_row() {
+        let true_grade = grade(
+            word_from_str(b"roses").unwrap(),
+            word_from_str(b"horse").unwrap(),
+        );
+        let lied = lies(true_grade, 1);
+        assert_eq!(lied.len(), 10);
+        for g in lied {
+            assert_eq!(n_altered(g, true_grade), 1);
+        }
+        assert_eq!(lies(true_grade, 0), vec![true_grade]);
+    }
+
+    #[test]
+    fn truth_is_never_consistent() {
+        let answers = [
+            "cigar", "rebut", "sissy", "humph", "awake", "blush", "focal",
+        ]
+        .map(|s| word_from_str(s.as_bytes()).unwrap());
+        let guess = word_from_str(b"trace").unwrap();
+        let observed = grade(guess, answers[0]);
+
+        assert!(!filter(guess, observed, 1, &answers).contains(&answers[0]));
+        assert!(entropy_after(guess, &answers, 1) > entropy_after(guess, &answers, 0));
+        assert_eq!(
+            entropy_after(guess, &answers, 0),
+            crate::squeeze::entropy_after::<1>(guess, &answers)
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use std::{
+    simd::{LaneCount, SupportedLaneCount},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread::scope,
+};
+
+use crate::{metric::Metric, rules::Rules, Grade, Word};
+
+/// The best second guess after the first guess was given some grade.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FollowUp {
+    pub grade: Grade,
+    /// Number of answers which give this grade.
+    pub n_candidates: usize,
+    pub best: Word,
+    pub score: f32,
+}
+
+/// Find the best follow-up to `first` for every grade it can get against `answers` under
+/// `rules`, in order of grade. In hard mode only follow-ups which reuse everything revealed by
+/// the grade are considered. Ties go to words which could be the answer.
+pub fn table<const L: usize>(
+    first: Word,
+    words: &[Word],
+    answers: &[Word],
+    metric: Metric,
+    rules: &Rules,
+    n_threads: usize,
+) -> Vec<FollowUp>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let buckets = rules
+        .bucket::<L>(first, answers)
+        .into_iter()
+        .enumerate()
+        .filter(|(_, b)| !b.is_empty())
+        .map(|(graded, b)| (graded as Grade, b))
+        .collect::<Vec<_>>();
+
+    let next_bucket = AtomicUsize::new(0);
+    let do_work = || {
+        let mut rows = Vec::new();
+        loop {
+            let i = next_bucket.fetch_add(1, Ordering::Relaxed);
+            if i >= buckets.len() {
+                break;
+            }
+            let (graded, ref solns) = buckets[i];
+            let mut best = (solns[0], metric.score_with::<L>(rules, solns[0], solns));
+            for &w in solns.iter().chain(words) {
+                if !rules.is_legal(w, &[(first, graded)]) {
+                    continue;
+                }
+                let score = metric.score_with::<L>(rules, w, solns);
+                if score < best.1 {
+                    best = (w, score);
+                }
+            }
+            rows.push(FollowUp {
+                grade: graded,
+                n_candidates: solns.len(),
+                best: best.0,
+                score: best.1,
+            });
+        }
+        rows
+    };
+
+    let mut rows = Vec::with_capacity(buckets.len());
+    scope(|s| {
+        let handles = (0..n_threads.max(1))
+            .map(|_| s.spawn(do_work))
+            .collect::<Vec<_>>();
+        for handle in handles {
+            rows.extend(handle.join().unwrap());
+        }
+    });
+    rows.sort_unstable_by_key(|r| r.grade);
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{squeeze::grade, word_from_str};
+
+    use super::*;
+
+    #[test]
+    fn covers_every_answer() {
+        let answers = [
+            "cigar", "rebut", "sissy", "humph", "awake", "blush", "focal", "evade", "naval",
+        ]
+        .map(|s| word_from_str(s.as_bytes()).unwrap());
+        let words = ["lions", "crane", "fuzzy"].map(|s| word_from_str(s.as_bytes()).unwrap());
+        let trace = word_from_str(b"trace").unwrap();
+
+        for hard in [false, true] {
+            let rules = Rules {
+                hard,
+                ..Rules::default()
+            };
+            let rows = table::<4>(trace, &words, &answers, Metric::WorstCase, &rules, 2);
+            assert_eq!(
+                rows.iter().map(|r| r.n_candidates).sum::<usize>(),
+                answers.len()
+            );
+            for r in rows {
+                assert!(answers.iter().any(|&a| grade(trace, a) == r.grade));
+                if r.n_candidates == 1 {
+                    // the only answer left is always the best guess
+                    assert_eq!(grade(trace, r.best), r.grade);
+                    assert_eq!(r.score, 1.0);
+                }
+            }
+        }
+    }
+}
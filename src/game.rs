@@ -0,0 +1,218 @@
+use std::{error::Error, fmt};
+
+use crate::{rules::Rules, str_from_word, Grade, Word, BLACK, GREEN, YELLOW};
+
+/// A single game of Wordle against a known answer.
+#[derive(Clone, Debug)]
+pub struct Game {
+    answer: Word,
+    rules: Rules,
+    history: Vec<(Word, Grade)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GuessError {
+    /// The game has already been won or lost.
+    Over,
+    NotAWord,
+    /// In hard mode, the guess ignores something revealed by this earlier guess.
+    IgnoresHint(Word),
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GuessError::Over => write!(f, "the game is over"),
+            GuessError::NotAWord => write!(f, "not in the word list"),
+            GuessError::IgnoresHint(prev) => write!(
+                f,
+                "hard mode: must use every hint from {}",
+                String::from_utf8_lossy(&str_from_word(*prev))
+            ),
+        }
+    }
+}
+
+impl Error for GuessError {}
+
+impl Game {
+    pub fn new(answer: Word, max_guesses: usize, hard: bool) -> Game {
+        Game {
+            answer,
+            rules: Rules {
+                max_guesses,
+                hard,
+                ..Rules::default()
+            },
+            history: Vec::with_capacity(max_guesses),
+        }
+    }
+
+    /// A game under `rules`, if they can be played.
+    pub fn with_rules(answer: Word, rules: Rules) -> Result<Game, String> {
+        rules.check()?;
+        Ok(Game {
+            answer,
+            rules,
+            history: Vec::with_capacity(rules.max_guesses),
+        })
+    }
+
+    /// A game with `guesses` already played, as when resuming a saved game.
+    pub fn resume(answer: Word, rules: Rules, guesses: &[Word]) -> Result<Game, String> {
+        let mut game = Game::with_rules(answer, rules)?;
+        for &guess in guesses {
+            let graded = rules.grade(guess, answer);
+            game.history.push((guess, graded));
+        }
+        Ok(game)
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    pub fn answer(&self) -> Word {
+        self.answer
+    }
+
+    pub fn max_guesses(&self) -> usize {
+        self.rules.max_guesses
+    }
+
+    pub fn is_hard(&self) -> bool {
+        self.rules.hard
+    }
+
+    pub fn history(&self) -> &[(Word, Grade)] {
+        &self.history
+    }
+
+    pub fn is_won(&self) -> bool {
+        self.history
+            .last()
+            .is_some_and(|&(_, g)| g == self.rules.all_green())
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.is_won() || self.history.len() >= self.rules.max_guesses
+    }
+
+    /// Play `guess`, which must be one of `words` if the rules check the dictionary, and return
+    /// its grade.
+    pub fn guess(&mut self, guess: Word, words: &[Word]) -> Result<Grade, GuessError> {
+        if self.is_over() {
+            return Err(GuessError::Over);
+        }
+        if self.rules.check_dictionary && !words.contains(&guess) {
+            return Err(GuessError::NotAWord);
+        }
+        if self.rules.hard {
+            if let Some(prev) = self.rules.ignored_hint(guess, &self.history) {
+                return Err(GuessError::IgnoresHint(prev));
+            }
+        }
+        let graded = self.rules.grade(guess, self.answer);
+        self.history.push((guess, graded));
+        Ok(graded)
+    }
+
+    /// The best color each letter has been given so far, or `None` if it has not been guessed.
+    pub fn keyboard(&self) -> [Option<Grade>; 26] {
+        let mut keys = [None; 26];
+        for &(guess, graded) in &self.history {
+            for i in 0..self.rules.word_length {
+                let c = ((guess >> (5 * i)) & 0x1f) as usize;
+                let color = (graded >> (2 * i)) & 0b11;
+                keys[c] = keys[c].max(Some(color));
+            }
+        }
+        keys
+    }
+}
+
+fn ansi_background(color: Option<Grade>) -> &'static str {
+    match color {
+        Some(GREEN) => "\x1b[1;30;42m",
+        Some(YELLOW) => "\x1b[1;30;43m",
+        Some(BLACK) => "\x1b[1;37;100m",
+        _ => "\x1b[1m",
+    }
+}
+
+/// A guess of `len` letters drawn as colored tiles with ANSI escape codes.
+pub fn render_row(guess: Word, graded: Grade, len: usize) -> String {
+    let mut row = String::new();
+    for i in 0..len {
+        let c = ((guess >> (5 * i)) & 0x1f) as u8 + b'a';
+        row.push_str(ansi_background(Some((graded >> (2 * i)) & 0b11)));
+        row.push(' ');
+        row.push(c.to_ascii_uppercase() as char);
+        row.push(' ');
+        row.push_str("\x1b[0m");
+    }
+    row
+}
+
+/// A QWERTY keyboard with every key colored by what is known about its letter.
+pub fn render_keyboard(keys: &[Option<Grade>; 26]) -> String {
+    let mut out = String::new();
+    for (indent, line) in ["", " ", "   "]
+        .into_iter()
+        .zip(["qwertyuiop", "asdfghjkl", "zxcvbnm"])
+    {
+        out.push_str(indent);
+        for c in line.bytes() {
+            out.push_str(ansi_background(keys[(c - b'a') as usize]));
+            out.push(c.to_ascii_uppercase() as char);
+            out.push_str("\x1b[0m ");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{word_from_str, ALL_GREEN};
+
+    use super::*;
+
+    #[test]
+    fn hard_mode_rejects_ignored_hints() {
+        let w = |s: &[u8]| word_from_str(s).unwrap();
+        let words = [w(b"trace"), w(b"crate"), w(b"lions"), w(b"react")];
+        let mut game = Game::new(w(b"crate"), 6, true);
+
+        assert_eq!(game.guess(w(b"zzzzz"), &words), Err(GuessError::NotAWord));
+        assert!(game.guess(w(b"trace"), &words).is_ok());
+        assert_eq!(
+            game.guess(w(b"lions"), &words),
+            Err(GuessError::IgnoresHint(w(b"trace")))
+        );
+        assert_eq!(game.guess(w(b"crate"), &words), Ok(ALL_GREEN));
+        assert!(game.is_won());
+        assert_eq!(game.guess(w(b"react"), &words), Err(GuessError::Over));
+
+        let keys = game.keyboard();
+        assert_eq!(keys[(b'c' - b'a') as usize], Some(GREEN));
+        assert_eq!(keys[(b'z' - b'a') as usize], None);
+
+        let rules = "check_dictionary = false\nmax_guesses = 1".parse().unwrap();
+        let mut game = Game::with_rules(w(b"crate"), rules).unwrap();
+        assert!(game.guess(w(b"zzzzz"), &words).is_ok());
+        assert!(game.is_over() && !game.is_won());
+        let rules = Rules {
+            word_length: 7,
+            ..rules
+        };
+        assert!(Game::with_rules(w(b"crate"), rules).is_err());
+
+        // every e of `speed` is yellow, though `abide` has only one
+        let rules = "hard = yes\nduplicates = anywhere".parse().unwrap();
+        let (speed, abide) = (w(b"speed"), w(b"abide"));
+        let mut game = Game::with_rules(abide, rules).unwrap();
+        assert!(game.guess(speed, &[speed, abide]).is_ok());
+        assert_eq!(game.guess(abide, &[speed, abide]), Ok(ALL_GREEN));
+    }
+}
@@ -0,0 +1,294 @@
+use std::{
+    collections::BinaryHeap,
+    simd::{LaneCount, SupportedLaneCount},
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+    thread::scope,
+};
+
+use crate::{opener::rank_words, policy::DupPolicy, rules::Rules, Grade, Word, BLACK, GREEN};
+
+/// Everything hard mode requires of later guesses: the positions already green, with their
+/// letters, and how many of each letter have been revealed. When every copy of a letter in the
+/// answer turns yellow, as under [`DupPolicy::Anywhere`], only its presence is revealed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Hints {
+    len: usize,
+    duplicates: DupPolicy,
+    greens: Grade,
+    letters: Word,
+    needed: [u8; 32],
+}
+
+impl Default for Hints {
+    fn default() -> Hints {
+        Hints::new(5, DupPolicy::Nyt)
+    }
+}
+
+impl Hints {
+    /// No hints yet, for words of `len` letters graded under `duplicates`.
+    pub fn new(len: usize, duplicates: DupPolicy) -> Hints {
+        Hints {
+            len,
+            duplicates,
+            greens: 0,
+            letters: 0,
+            needed: [0; 32],
+        }
+    }
+
+    /// Whether `guess` keeps every green letter in place and reuses every revealed letter at
+    /// least as many times as it was revealed.
+    pub fn allows(&self, guess: Word) -> bool {
+        let mut counts = [0u8; 32];
+        for i in 0..self.len {
+            let c = (guess >> (5 * i)) & 0x1f;
+            if self.greens & (GREEN << (2 * i)) != 0 && c != (self.letters >> (5 * i)) & 0x1f {
+                return false;
+            }
+            counts[c as usize] += 1;
+        }
+        counts
+            .iter()
+            .zip(&self.needed)
+            .all(|(n, needed)| n >= needed)
+    }
+
+    /// The hints after `guess` is graded `graded`.
+    pub fn after(&self, guess: Word, graded: Grade) -> Hints {
+        let mut next = *self;
+        let mut revealed = [0u8; 32];
+        for i in 0..self.len {
+            let c = (guess >> (5 * i)) & 0x1f;
+            match (graded >> (2 * i)) & 0b11 {
+                BLACK => continue,
+                GREEN => {
+                    next.greens |= GREEN << (2 * i);
+                    next.letters = next.letters & !(0x1f << (5 * i)) | c << (5 * i);
+                }
+                _ => {}
+            }
+            revealed[c as usize] = match self.duplicates {
+                DupPolicy::Nyt => revealed[c as usize] + 1,
+                DupPolicy::Anywhere => 1,
+            };
+        }
+        for (needed, revealed) in next.needed.iter_mut().zip(revealed) {
+            *needed = (*needed).max(revealed);
+        }
+        next
+    }
+}
+
+/// Whether `guess` may be played in hard mode after `prev` was graded `prev_grade`.
+pub fn is_legal(guess: Word, prev: Word, prev_grade: Grade) -> bool {
+    Hints::default().after(prev, prev_grade).allows(guess)
+}
+
+/// The entropy left after opening with `first` and then playing, for each grade, the hard-mode
+/// legal word in `words` which leaves the least entropy under `rules`.
+pub fn entropy_after_adaptive<const L: usize>(
+    first: Word,
+    words: &[Word],
+    answers: &[Word],
+    rules: &Rules,
+) -> f32
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    adaptive_below::<L>(first, words, answers, rules, f32::INFINITY).unwrap_or(f32::INFINITY)
+}
+
+/// [`entropy_after_adaptive`], or `None` as soon as it is sure not to come out below
+/// `threshold`.
+///
+/// Grades are tried largest bucket first, and every bucket not yet tried is assumed to be split
+/// as evenly as any legal second guess possibly could, which is the best case.
+fn adaptive_below<const L: usize>(
+    first: Word,
+    words: &[Word],
+    answers: &[Word],
+    rules: &Rules,
+    threshold: f32,
+) -> Option<f32>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    // a legal second guess keeps every green, so only the other positions can split a bucket
+    let n_splits = |graded: Grade| {
+        let greens = (0..rules.word_length)
+            .filter(|i| (graded >> (2 * i)) & 0b11 == GREEN)
+            .count();
+        3usize.pow((rules.word_length - greens) as u32)
+    };
+    let mut buckets = rules
+        .bucket::<L>(first, answers)
+        .into_iter()
+        .enumerate()
+        .filter(|(_, b)| b.len() > 1)
+        .map(|(graded, b)| (graded as Grade, b))
+        .collect::<Vec<_>>();
+    buckets.sort_unstable_by_key(|(_, b)| usize::MAX - b.len());
+
+    let n_answers = answers.len() as f32;
+    let mut bound = buckets
+        .iter()
+        .map(|&(graded, ref b)| even_split(b.len(), n_splits(graded)))
+        .sum::<f32>()
+        / n_answers;
+    let hints = Hints::new(rules.word_length, rules.duplicates);
+    for &(graded, ref b) in &buckets {
+        if bound >= threshold {
+            return None;
+        }
+        let hints = hints.after(first, graded);
+        let least = even_split(b.len(), n_splits(graded));
+        let mut best = f32::INFINITY;
+        for &w in words.iter().filter(|&&w| hints.allows(w)) {
+            best = best.min(rules.entropy_after::<L>(w, b) * b.len() as f32);
+            // nothing splits the bucket more evenly
+            if best <= least {
+                break;
+            }
+        }
+        bound += (best - least) / n_answers;
+    }
+    (bound < threshold).then_some(bound)
+}
+
+/// The least `n * entropy` a guess can leave among `n` answers by splitting them `n_splits`
+/// ways: as many as possible in each, as evenly as possible.
+fn even_split(n: usize, n_splits: usize) -> f32 {
+    let (q, r) = (n / n_splits, n % n_splits);
+    let bits = |c: usize| {
+        if c > 1 {
+            (c as f32).log2() * c as f32
+        } else {
+            0.0
+        }
+    };
+    r as f32 * bits(q + 1) + (n_splits - r) as f32 * bits(q)
+}
+
+/// Find the `top_k` best of the `pool` best lone openers by the entropy left after a hard-mode
+/// second guess chosen per grade, best first.
+///
+/// As in [`opener::search`](crate::opener::search), entropies are shared between threads in
+/// fixed point, and an opener is dropped once even the best case for the grades left to try
+/// cannot beat the `top_k`-th best found so far.
+pub fn search<const L: usize>(
+    words: &[Word],
+    answers: &[Word],
+    pool: usize,
+    top_k: usize,
+    n_threads: usize,
+    rules: &Rules,
+) -> Vec<(Word, f32)>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let mut ranked = rank_words::<L>(words, answers, rules);
+    ranked.truncate(pool);
+    if top_k == 0 {
+        return Vec::new();
+    }
+
+    let threshold = AtomicU64::new(u64::MAX);
+    let next_start = AtomicUsize::new(0);
+    let do_work = || {
+        // max-heap keyed by fixed-point entropy
+        let mut best = BinaryHeap::new();
+        loop {
+            let i = next_start.fetch_add(1, Ordering::Relaxed);
+            if i >= ranked.len() {
+                break;
+            }
+            let first = ranked[i].0;
+            let below = threshold.load(Ordering::Relaxed) as f32 / 1e7;
+            let Some(entropy) = adaptive_below::<L>(first, words, answers, rules, below) else {
+                continue;
+            };
+            best.push(((entropy * 1e7) as u64, first));
+            if best.len() > top_k {
+                best.pop();
+            }
+            if best.len() == top_k {
+                threshold.fetch_min(best.peek().unwrap().0, Ordering::Relaxed);
+            }
+        }
+        best.into_vec()
+    };
+
+    let mut found = Vec::new();
+    scope(|s| {
+        let handles = (0..n_threads.max(1))
+            .map(|_| s.spawn(do_work))
+            .collect::<Vec<_>>();
+        for handle in handles {
+            found.extend(handle.join().unwrap());
+        }
+    });
+    found.sort_unstable();
+    found
+        .into_iter()
+        .take(top_k)
+        .map(|(key, w)| (w, key as f32 / 1e7))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{squeeze::grade, word_from_str};
+
+    use super::*;
+
+    #[test]
+    fn keeps_greens_and_yellows() {
+        let trace = word_from_str(b"trace").unwrap();
+        let graded = grade(trace, word_from_str(b"crate").unwrap());
+
+        assert!(is_legal(word_from_str(b"crate").unwrap(), trace, graded));
+        assert!(is_legal(trace, trace, graded));
+        // the green r and a have moved
+        assert!(!is_legal(word_from_str(b"caret").unwrap(), trace, graded));
+        // no c at all
+        assert!(!is_legal(word_from_str(b"irate").unwrap(), trace, graded));
+    }
+
+    #[test]
+    fn needs_repeated_letters() {
+        let eerie = word_from_str(b"eerie").unwrap();
+        let graded = grade(eerie, word_from_str(b"geese").unwrap());
+
+        assert!(is_legal(word_from_str(b"geese").unwrap(), eerie, graded));
+        // only two e's
+        assert!(!is_legal(word_from_str(b"beige").unwrap(), eerie, graded));
+    }
+
+    #[test]
+    fn search_matches_brute_force() {
+        let answers = [
+            "cigar", "rebut", "sissy", "humph", "awake", "blush", "focal", "evade", "naval",
+            "serve", "heath", "dwarf", "model", "karma", "stink", "grade",
+        ]
+        .map(|s| word_from_str(s.as_bytes()).unwrap());
+        let mut words = answers.to_vec();
+        words.extend(
+            ["trace", "lions", "salet", "fuzzy"].map(|s| word_from_str(s.as_bytes()).unwrap()),
+        );
+        let rules = Rules::default();
+
+        let mut brute = words
+            .iter()
+            .map(|&w| entropy_after_adaptive::<4>(w, &words, &answers, &rules))
+            .collect::<Vec<_>>();
+        brute.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let found = search::<4>(&words, &answers, words.len(), 3, 2, &rules);
+        assert_eq!(found.len(), 3);
+        for (&(w, ent), &expected) in found.iter().zip(&brute) {
+            assert!((ent - expected).abs() < 1e-5, "{ent} != {expected}");
+            assert!((ent - entropy_after_adaptive::<4>(w, &words, &answers, &rules)).abs() < 1e-5);
+        }
+    }
+}
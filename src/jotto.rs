@@ -0,0 +1,109 @@
+use std::simd::{
+    cmp::{SimdPartialEq, SimdPartialOrd},
+    LaneCount, Simd, SupportedLaneCount,
+};
+
+use crate::{scoring::Scorer, Word};
+
+/// Number of distinct feedbacks: from no letters shared to all five.
+pub const N_FEEDBACKS: usize = 6;
+
+/// Number of letters `guess` shares with `soln`, counting repeated letters as many times as
+/// they appear in both.
+pub fn grade(guess: Word, soln: Word) -> u8 {
+    let mut bank = 0u64;
+    let mut soln2 = soln;
+    for _ in 0..5 {
+        bank += 1 << (2 * (soln2 & 0x1f));
+        soln2 >>= 5;
+    }
+
+    let mut shared = 0;
+    let mut guess2 = guess;
+    for _ in 0..5 {
+        let c = guess2 & 0x1f;
+        if (bank >> (2 * c)) & 0b11 > 0 {
+            bank -= 1 << (2 * c);
+            shared += 1;
+        }
+        guess2 >>= 5;
+    }
+    shared
+}
+
+pub fn gradel<const L: usize>(words: Simd<Word, L>, solns: Simd<Word, L>) -> Simd<u32, L>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    // split letter bank since it needs 52 bits
+    let mut bank = [Simd::<u32, L>::splat(0); 2];
+    let mut soln2 = solns;
+    let sixteen = Simd::splat(16);
+    for _ in 0..5 {
+        let sc = soln2 & Simd::splat(0x1f);
+        let is_first_sixteen = sc.simd_lt(sixteen);
+        bank[0] += is_first_sixteen.select(Simd::splat(1) << (Simd::splat(2) * sc), Simd::splat(0));
+        bank[1] += (!is_first_sixteen).select(
+            Simd::splat(1) << (Simd::splat(2) * (sc - sixteen)),
+            Simd::splat(0),
+        );
+        soln2 >>= 5;
+    }
+
+    let mut shared = Simd::splat(0);
+    let mut guess2 = words;
+    for _ in 0..5 {
+        let c = guess2 & Simd::splat(0x1f);
+        let is_first_sixteen = c.simd_lt(sixteen);
+        let offset_c = is_first_sixteen.select(c, c - sixteen);
+        let n_left = (is_first_sixteen.select(bank[0], bank[1]) >> (Simd::splat(2) * offset_c))
+            & Simd::splat(0b11);
+        let hit = n_left.simd_ne(Simd::splat(0));
+        shared += hit.select(Simd::splat(1), Simd::splat(0));
+
+        let subs = Simd::splat(1) << (Simd::splat(2) * offset_c);
+        bank[0] -= (hit & is_first_sixteen).select(subs, Simd::splat(0));
+        bank[1] -= (hit & !is_first_sixteen).select(subs, Simd::splat(0));
+        guess2 >>= 5;
+    }
+    shared
+}
+
+/// Jotto, where the only feedback is the number of letters shared with the answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Jotto;
+
+impl Scorer for Jotto {
+    fn n_feedbacks(&self) -> usize {
+        N_FEEDBACKS
+    }
+
+    fn score(&self, guess: Word, soln: Word) -> usize {
+        grade(guess, soln).into()
+    }
+
+    fn scorel<const L: usize>(&self, guesses: Simd<Word, L>, solns: Simd<Word, L>) -> [usize; L]
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        gradel(guesses, solns)
+            .to_array()
+            .map(|shared| shared as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::word_from_str;
+
+    use super::*;
+
+    #[test]
+    fn counts_repeats_once_each() {
+        let w = |s: &[u8]| word_from_str(s).unwrap();
+        assert_eq!(grade(w(b"horse"), w(b"horse")), 5);
+        assert_eq!(grade(w(b"roses"), w(b"horse")), 4);
+        assert_eq!(grade(w(b"eerie"), w(b"geese")), 3);
+        assert_eq!(grade(w(b"fuzzy"), w(b"cigar")), 0);
+    }
+}
@@ -2,22 +2,55 @@
 
 use std::{
     array,
+    error::Error,
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
     time::{Duration, Instant},
 };
 
 pub type Grade = u16;
 pub type Word = u32;
 
+pub mod absurdle;
+pub mod analysis;
+pub mod daily;
+pub mod disjoint;
+pub mod equiv;
+pub mod explain;
+pub mod fibble;
+pub mod followup;
+pub mod game;
+pub mod hard;
+pub mod jotto;
+pub mod mastermind;
+pub mod metric;
+pub mod minimax;
+pub mod multi;
 pub mod naive;
+pub mod opener;
 pub mod packed;
+pub mod partition;
+pub mod peaks;
+pub mod policy;
+pub mod preimage;
+pub mod query;
+pub mod rng;
+pub mod rules;
+pub mod scoring;
 pub mod sensible;
+pub mod share;
 pub mod squeeze;
+pub mod stats;
+pub mod tournament;
+pub mod winprob;
 
 const GREEN: u16 = 0b10;
 const YELLOW: u16 = 0b01;
 const BLACK: u16 = 0b00;
 
 pub const N_GRADES: usize = 0b1010101011;
+pub const ALL_GREEN: Grade = GREEN * 0b0101010101;
 
 pub fn word_from_str(s: &[u8]) -> Option<Word> {
     if s.len() != 5 {
@@ -37,6 +70,39 @@ pub fn str_from_word(word: Word) -> [u8; 5] {
     array::from_fn(|i| ((word >> (5 * i)) & 0x1f) as u8 + b'a')
 }
 
+/// Parse a grade written as five of `g` (green), `y` (yellow) and `b` (black).
+pub fn grade_from_str(s: &[u8]) -> Option<Grade> {
+    if s.len() != 5 {
+        return None;
+    }
+    let mut g = 0u16;
+    for (i, &c) in s.iter().enumerate() {
+        g |= match c {
+            b'g' => GREEN,
+            b'y' => YELLOW,
+            b'b' => BLACK,
+            _ => return None,
+        } << (2 * i);
+    }
+    Some(g)
+}
+
+pub fn str_from_grade(grade: Grade) -> [u8; 5] {
+    array::from_fn(|i| match (grade >> (2 * i)) & 0b11 {
+        GREEN => b'g',
+        YELLOW => b'y',
+        _ => b'b',
+    })
+}
+
+/// Read a file containing one five-letter word per line.
+pub fn read_words(path: impl AsRef<Path>) -> Result<Vec<Word>, Box<dyn Error>> {
+    BufReader::new(File::open(path)?)
+        .lines()
+        .map(|w| word_from_str(w?.as_bytes()).ok_or("invalid word".into()))
+        .collect()
+}
+
 pub fn stopwatch<F: FnOnce() -> R, R>(f: F) -> (R, Duration) {
     let tic = Instant::now();
     let res = f();
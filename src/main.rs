@@ -1,152 +1,75 @@
 #![feature(portable_simd)]
 
-use core::str;
-use std::{
-    array,
-    fs::File,
-    io::{BufRead, BufReader},
-    simd::Simd,
-    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
-    thread::{available_parallelism, scope},
-};
+use std::thread::available_parallelism;
 
 use wordle::{
-    squeeze::{grade, gradel},
-    str_from_word, word_from_str, Word, N_GRADES,
+    daily::{parse_puzzle, unused},
+    hard, opener,
+    rules::Rules,
 };
 
 const L: usize = 8;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args = std::env::args().collect::<Vec<_>>();
-    if args.len() < 3 {
-        println!("wordle: usage: wordle <answers> <words>");
+    let hard_mode = std::env::args().any(|a| a == "--hard");
+    let mut args = std::env::args()
+        .filter(|a| a != "--hard")
+        .collect::<Vec<_>>();
+    // answers used before this puzzle are left out
+    let mut from = None;
+    if let Some(i) = args.iter().position(|a| a == "--from") {
+        let s = args.get(i + 1).ok_or("--from needs a date or #number")?;
+        from = Some(parse_puzzle(s).ok_or("expected a date YYYY-MM-DD or #number")?);
+        args.drain(i..i + 2);
     }
-    let answers: Vec<Word> = BufReader::new(File::open(&args[1])?)
-        .lines()
-        .map(|w| word_from_str(w?.as_bytes()).ok_or("invalid word".into()))
-        .collect::<Result<Vec<Word>, Box<dyn std::error::Error>>>()?;
-
-    let mut words_human: Vec<[u8; 5]> = Vec::with_capacity(12948);
-    let mut words = Vec::with_capacity(12948);
-    let initial_entropy = (answers.len() as f32).log2();
-    let mut word_bits_left = Vec::with_capacity(12948);
-
-    for s in BufReader::new(File::open(&args[2])?).lines() {
-        let s = s?;
-        if s.len() != 5 || !s.bytes().all(|b| b.is_ascii_lowercase()) {
-            return Err("bad word".into());
-        }
-        let sb = s.as_bytes();
-        words_human.push(array::from_fn(|i| sb[i]));
-        let word = unsafe { word_from_str(sb).unwrap_unchecked() };
-        words.push(word);
-        word_bits_left.push((word, wordle::squeeze::entropy_after::<L>(word, &answers)));
+    let mut rules = Rules::default();
+    if let Some(i) = args.iter().position(|a| a == "--rules") {
+        rules = Rules::load(args.get(i + 1).ok_or("--rules needs a file")?)?;
+        args.drain(i..i + 2);
     }
-
-    word_bits_left.sort_unstable_by(|&(_, e1), &(_, e2)| e1.partial_cmp(&e2).unwrap());
-
-    let best_entropy = AtomicU64::new((word_bits_left[0].1 * 1e7) as u64);
-    let next_start = AtomicUsize::new(0);
-
-    println!("n_threads = {}", available_parallelism().unwrap());
-    let do_work = |_tid: usize| {
-        let mut opener_value = Vec::with_capacity(
-            words.len() * (words.len() - 1) / (2 * available_parallelism().unwrap().get()),
+    if args.len() < 3 {
+        println!(
+            "wordle: usage: wordle <answers> <words> [n_words] [top_k] [pool] [--rules file] [--hard] [--from date]"
         );
-        let (prefix, simds, suffix) = answers.as_simd::<L>();
-        let mut possible_solns: [Vec<Word>; N_GRADES] = array::from_fn(|_| Vec::new()); // map from grades to possible solns
-        loop {
-            let i = next_start.fetch_add(1, Ordering::Relaxed);
-            if i >= words.len() {
-                break;
-            }
-            let (w0, el0) = word_bits_left[i];
-            // println!(
-            //     "thread {tid} start word {}",
-            //     str::from_utf8(&str_from_word(w0)).unwrap()
-            // );
-
-            for &answer in prefix {
-                possible_solns[grade(w0, answer) as usize].push(answer);
-            }
-            for &answer in suffix {
-                possible_solns[grade(w0, answer) as usize].push(answer);
-            }
-            for &answer in simds {
-                let grades = gradel(Simd::splat(w0), answer);
-                for (graded, answer) in grades.to_array().into_iter().zip(answer.to_array()) {
-                    possible_solns[graded as usize].push(answer);
-                }
-            }
-
-            for &(w1, el1) in &word_bits_left[..i] {
-                if el0 - (initial_entropy - el1)
-                    >= best_entropy.load(Ordering::Relaxed) as f32 / 1e7
-                {
-                    // cannot get extra info out
-                    // we sorted the words, so we won't have any more anyway
-                    break;
-                }
-
-                let mut rem_entropy = 0.0;
-                for possibles in possible_solns.iter().filter(|v| v.len() > 1) {
-                    rem_entropy +=
-                        wordle::squeeze::entropy_after::<L>(w1, possibles) * possibles.len() as f32
-                }
-                rem_entropy /= answers.len() as f32;
-                best_entropy.fetch_min((rem_entropy * 1e7) as u64, Ordering::Relaxed);
-                // println!(
-                //     "{}, {}: {rem_entropy}",
-                //     str::from_utf8(&str_from_word(w0)).unwrap(),
-                //     str::from_utf8(&str_from_word(w1)).unwrap()
-                // );
-
-                opener_value.push(((w0, w1), rem_entropy));
-            }
-
-            // clean up but don't free the memory
-            for v in &mut possible_solns {
-                v.clear();
-            }
+    }
+    rules.hard |= hard_mode;
+    let mut answers = rules.read_words(&args[1])?;
+    if let Some(number) = from {
+        answers = unused(&answers, number).to_vec();
+        println!("{} answers left from puzzle #{number}", answers.len());
+    }
+    let words = rules.read_words(&args[2])?;
+    let n_words = args.get(3).map_or(Ok(2), |s| s.parse())?;
+    let top_k = args.get(4).map_or(Ok(10), |s| s.parse())?;
+    // hard mode scores every second guess against every grade of each opener, so by default
+    // only the best lone openers are tried
+    let default_pool = if rules.hard { 100 } else { words.len() };
+    let pool = args.get(5).map_or(Ok(default_pool), |s| s.parse())?;
+
+    let n_threads = available_parallelism().unwrap().get();
+    println!("n_threads = {n_threads}");
+    if rules.hard {
+        // the second guess depends on the first's grade, so only the first is fixed
+        let best = hard::search::<L>(&words, &answers, pool, top_k, n_threads, &rules);
+        println!("Top {top_k} in hard mode:");
+        for &(w, entropy_left) in &best {
+            println!("{}: {entropy_left}", rules.str_from_word(w));
         }
-        opener_value
-    };
+        return Ok(());
+    }
 
-    let mut opener_value = Vec::with_capacity(words.len() * (words.len() - 1) / 2);
-    scope(|s| {
-        let handles = (0..available_parallelism().unwrap().get())
-            .map(|tid| s.spawn(move || do_work(tid)))
-            .collect::<Vec<_>>();
-        for handle in handles {
-            opener_value.extend(handle.join().unwrap());
-        }
-    });
+    let best = opener::search::<L>(n_words, &words, &answers, pool, top_k, n_threads, &rules);
 
-    opener_value.sort_unstable_by(|&(_, e1), &(_, e2)| e1.partial_cmp(&e2).unwrap());
-    println!("Top 10:");
-    for &((w0, w1), entropy_left) in opener_value.iter().take(10) {
+    println!("Top {top_k}:");
+    for (set, entropy_left) in &best {
         println!(
-            "{}, {}: {entropy_left}",
-            str::from_utf8(&str_from_word(w0)).unwrap(),
-            str::from_utf8(&str_from_word(w1)).unwrap()
+            "{}: {entropy_left}",
+            set.iter()
+                .map(|&w| rules.str_from_word(w))
+                .collect::<Vec<_>>()
+                .join(", ")
         );
     }
 
-    let trace_w = word_from_str(b"trace").unwrap();
-    let lions_w = word_from_str(b"lions").unwrap();
-    let (i, ent) = opener_value
-        .iter()
-        .enumerate()
-        .find_map(|(i, &((w0, w1), ent))| {
-            ((w0 == trace_w && w1 == lions_w) || (w0 == lions_w && w1 == trace_w))
-                .then_some((i, ent))
-        })
-        .unwrap();
-    println!(
-        "trace, lions is the {}-th best word with entropy {ent}",
-        i + 1
-    );
-
     Ok(())
 }
@@ -0,0 +1,230 @@
+use std::simd::{LaneCount, Simd, SupportedLaneCount};
+
+use crate::{
+    scoring::Scorer,
+    squeeze::{grade_n, gradel_n},
+    Grade, Word, BLACK, GREEN, YELLOW,
+};
+
+/// Symbols are written with these characters, in order.
+pub const SYMBOLS: &[u8; 32] = b"0123456789abcdefghijklmnopqrstuv";
+pub const MAX_LEN: usize = 6;
+
+/// How a guess is scored against the secret code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Feedback {
+    /// A color for every position, exactly as in Wordle.
+    Colors,
+    /// Only the number of black pegs (right symbol, right place) and white pegs (right symbol,
+    /// wrong place), as in Mastermind or Bulls and Cows.
+    Pegs,
+}
+
+/// A guessing game over codes of `len` symbols drawn from the first `n_symbols` of
+/// [`SYMBOLS`], packed five bits per symbol like a [`Word`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Mastermind {
+    pub len: usize,
+    pub n_symbols: u32,
+    pub feedback: Feedback,
+}
+
+impl Mastermind {
+    pub fn new(len: usize, n_symbols: u32, feedback: Feedback) -> Option<Mastermind> {
+        ((1..=MAX_LEN).contains(&len) && (1..=32).contains(&n_symbols)).then_some(Mastermind {
+            len,
+            n_symbols,
+            feedback,
+        })
+    }
+
+    /// Classic Mastermind: four pegs of six colors.
+    pub fn classic() -> Mastermind {
+        Mastermind {
+            len: 4,
+            n_symbols: 6,
+            feedback: Feedback::Pegs,
+        }
+    }
+
+    /// Bulls and Cows: four distinct digits. Use `all_codes(true)` for the candidates.
+    pub fn bulls_and_cows() -> Mastermind {
+        Mastermind {
+            len: 4,
+            n_symbols: 10,
+            feedback: Feedback::Pegs,
+        }
+    }
+
+    pub fn code_from_str(&self, s: &[u8]) -> Option<Word> {
+        if s.len() != self.len {
+            return None;
+        }
+        let mut code = 0;
+        for (i, c) in s.iter().enumerate() {
+            let symbol = SYMBOLS.iter().position(|s| s == c)? as u32;
+            if symbol >= self.n_symbols {
+                return None;
+            }
+            code |= symbol << (5 * i);
+        }
+        Some(code)
+    }
+
+    pub fn str_from_code(&self, code: Word) -> String {
+        (0..self.len)
+            .map(|i| SYMBOLS[((code >> (5 * i)) & 0x1f) as usize] as char)
+            .collect()
+    }
+
+    /// Every code, optionally only those with no repeated symbol.
+    /// There are `n_symbols.pow(len)` of them, so keep both small.
+    pub fn all_codes(&self, distinct: bool) -> Vec<Word> {
+        let mut codes = vec![0];
+        for i in 0..self.len {
+            codes = codes
+                .into_iter()
+                .flat_map(|code| {
+                    (0..self.n_symbols)
+                        .filter(move |&s| {
+                            !distinct || (0..i).all(|j| (code >> (5 * j)) & 0x1f != s)
+                        })
+                        .map(move |s| code | (s << (5 * i)))
+                })
+                .collect();
+        }
+        codes
+    }
+
+    fn feedback_of(&self, graded: Grade) -> usize {
+        match self.feedback {
+            Feedback::Colors => graded as usize,
+            Feedback::Pegs => {
+                let (black, white) = pegs(graded, self.len);
+                black * (self.len + 1) + white
+            }
+        }
+    }
+
+    /// Parse feedback typed as a color per position (`g`, `y` or `b`) or as black and white peg
+    /// counts separated by whitespace.
+    pub fn feedback_from_str(&self, s: &str) -> Option<usize> {
+        match self.feedback {
+            Feedback::Colors => {
+                let s = s.trim().as_bytes();
+                if s.len() != self.len {
+                    return None;
+                }
+                let mut graded = 0;
+                for (i, c) in s.iter().enumerate() {
+                    graded |= match c {
+                        b'g' => GREEN,
+                        b'y' => YELLOW,
+                        b'b' => BLACK,
+                        _ => return None,
+                    } << (2 * i);
+                }
+                Some(graded as usize)
+            }
+            Feedback::Pegs => {
+                let mut counts = s.split_whitespace().map(|n| n.parse::<usize>());
+                let (Some(Ok(black)), Some(Ok(white)), None) =
+                    (counts.next(), counts.next(), counts.next())
+                else {
+                    return None;
+                };
+                (black + white <= self.len).then_some(black * (self.len + 1) + white)
+            }
+        }
+    }
+}
+
+impl Scorer for Mastermind {
+    fn n_feedbacks(&self) -> usize {
+        match self.feedback {
+            Feedback::Colors => ((GREEN as usize) << (2 * self.len)) / 3 + 1,
+            Feedback::Pegs => (self.len + 1) * (self.len + 1),
+        }
+    }
+
+    fn score(&self, guess: Word, soln: Word) -> usize {
+        let graded = match self.len {
+            1 => grade_n::<1>(guess, soln),
+            2 => grade_n::<2>(guess, soln),
+            3 => grade_n::<3>(guess, soln),
+            4 => grade_n::<4>(guess, soln),
+            5 => grade_n::<5>(guess, soln),
+            _ => grade_n::<6>(guess, soln),
+        };
+        self.feedback_of(graded)
+    }
+
+    fn scorel<const L: usize>(&self, guesses: Simd<Word, L>, solns: Simd<Word, L>) -> [usize; L]
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        let grades = match self.len {
+            1 => gradel_n::<L, 1>(guesses, solns),
+            2 => gradel_n::<L, 2>(guesses, solns),
+            3 => gradel_n::<L, 3>(guesses, solns),
+            4 => gradel_n::<L, 4>(guesses, solns),
+            5 => gradel_n::<L, 5>(guesses, solns),
+            _ => gradel_n::<L, 6>(guesses, solns),
+        };
+        grades.to_array().map(|g| self.feedback_of(g as Grade))
+    }
+}
+
+/// The number of black and white pegs equivalent to a grade of a code of `len` symbols.
+pub fn pegs(graded: Grade, len: usize) -> (usize, usize) {
+    let mut black = 0;
+    let mut white = 0;
+    for i in 0..len {
+        match (graded >> (2 * i)) & 0b11 {
+            GREEN => black += 1,
+            YELLOW => white += 1,
+            _ => (),
+        }
+    }
+    (black, white)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scoring::{best_guess, filter};
+
+    use super::*;
+
+    #[test]
+    fn classic_pegs() {
+        let game = Mastermind::classic();
+        let code = |s: &[u8]| game.code_from_str(s).unwrap();
+        assert_eq!(game.all_codes(false).len(), 1296);
+        // 1122 against 1213: one black, two white
+        assert_eq!(
+            game.score(code(b"1122"), code(b"1213")),
+            game.feedback_from_str("1 2").unwrap()
+        );
+        assert_eq!(game.str_from_code(code(b"5401")), "5401");
+        assert_eq!(game.code_from_str(b"1236"), None);
+    }
+
+    #[test]
+    fn bulls_and_cows_solves() {
+        let game = Mastermind::bulls_and_cows();
+        let codes = game.all_codes(true);
+        assert_eq!(codes.len(), 5040);
+
+        let secret = game.code_from_str(b"4271").unwrap();
+        let mut candidates = codes.clone();
+        for _ in 0..10 {
+            let guess = best_guess::<8>(&game, &[], &candidates).unwrap();
+            if guess == secret {
+                return;
+            }
+            candidates = filter::<8>(&game, guess, game.score(guess, secret), &candidates);
+            assert!(candidates.contains(&secret));
+        }
+        panic!("did not find the secret");
+    }
+}
@@ -0,0 +1,66 @@
+use std::{
+    simd::{LaneCount, SupportedLaneCount},
+    str::FromStr,
+};
+
+use crate::{rules::Rules, squeeze::grade_counts, Word};
+
+/// A way of scoring a guess by how it splits the possible answers. Lower scores are better.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Metric {
+    /// Expected bits left to identify the answer.
+    Entropy,
+    /// Expected number of answers still possible.
+    ExpectedSize,
+    /// Number of answers still possible in the worst case.
+    WorstCase,
+}
+
+impl Metric {
+    pub fn score<const L: usize>(self, guess: Word, solns: &[Word]) -> f32
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        self.of_counts(&grade_counts::<L>(guess, solns), solns.len())
+    }
+
+    /// [`Metric::score`] under `rules`.
+    pub fn score_with<const L: usize>(self, rules: &Rules, guess: Word, solns: &[Word]) -> f32
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        self.of_counts(&rules.grade_counts::<L>(guess, solns), solns.len())
+    }
+
+    fn of_counts(self, counts: &[u16], n: usize) -> f32 {
+        match self {
+            Metric::Entropy => {
+                counts
+                    .iter()
+                    .filter(|&&c| c > 1)
+                    .map(|&c| (c as f32).log2() * c as f32)
+                    .sum::<f32>()
+                    / n as f32
+            }
+            Metric::ExpectedSize => {
+                counts.iter().map(|&c| c as f32 * c as f32).sum::<f32>() / n as f32
+            }
+            Metric::WorstCase => counts.iter().copied().max().unwrap_or(0).into(),
+        }
+    }
+}
+
+impl FromStr for Metric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "entropy" => Ok(Metric::Entropy),
+            "expected" => Ok(Metric::ExpectedSize),
+            "worst" => Ok(Metric::WorstCase),
+            _ => Err(format!(
+                "unknown metric {s:?}; expected entropy, expected or worst"
+            )),
+        }
+    }
+}
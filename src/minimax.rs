@@ -0,0 +1,382 @@
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+    simd::{LaneCount, SupportedLaneCount},
+};
+
+use crate::{
+    equiv::{distinct, undominated},
+    rules::Rules,
+    Grade, Word,
+};
+
+/// A strategy: the guess to make, then the strategy to follow for each grade but all green.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tree {
+    pub guess: Word,
+    pub branches: Vec<(Grade, Tree)>,
+}
+
+impl Tree {
+    /// The most guesses the strategy takes to find any answer.
+    pub fn depth(&self) -> usize {
+        1 + self
+            .branches
+            .iter()
+            .map(|(_, t)| t.depth())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The number of guesses taken to find `answer` under `rules`, or `None` if the strategy
+    /// doesn't.
+    pub fn guesses_for(&self, answer: Word, rules: &Rules) -> Option<usize> {
+        let graded = rules.grade(self.guess, answer);
+        if graded == rules.all_green() {
+            return Some(1);
+        }
+        let (_, next) = self.branches.iter().find(|&&(g, _)| g == graded)?;
+        next.guesses_for(answer, rules).map(|n| n + 1)
+    }
+}
+
+/// A strategy with the fewest guesses in the worst case the search could find.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Solution {
+    pub tree: Tree,
+    pub depth: usize,
+    /// No strategy can always win in fewer guesses than this. When it equals `depth`, the tree
+    /// is optimal.
+    pub lower_bound: usize,
+    /// The answers which take `depth` guesses.
+    pub worst: Vec<Word>,
+}
+
+/// Searches for strategies which always win within some number of guesses. Against each set of
+/// candidates only the `width` guesses with the smallest worst-case bucket are tried, so failing
+/// to find a strategy only proves none exists if `width` covers every word and candidate.
+/// Guesses are graded, and in hard mode restricted, under `rules`.
+pub struct Minimax<'a> {
+    words: &'a [Word],
+    width: usize,
+    rules: Rules,
+    memo: HashMap<(Vec<Word>, usize), Option<Tree>>,
+}
+
+/// The largest bucket `guess` leaves among `candidates`, and how many buckets there are.
+fn worst_and_count<const L: usize>(
+    guess: Word,
+    candidates: &[Word],
+    rules: &Rules,
+) -> (usize, usize)
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    rules
+        .grade_counts::<L>(guess, candidates)
+        .into_iter()
+        .filter(|&n| n > 0)
+        .fold((0, 0), |(worst, count), n| (worst.max(n.into()), count + 1))
+}
+
+/// The most answers any strategy could find within `depth` guesses, if no guess ever splits the
+/// candidates into more than `max_grades` buckets.
+fn capacity(max_grades: usize, depth: usize) -> usize {
+    (1..depth).fold(1usize.min(depth), |cap, _| {
+        cap.saturating_mul(max_grades - 1).saturating_add(1)
+    })
+}
+
+/// The fewest guesses any strategy could need to always find one of `candidates`, by counting:
+/// no guess splits them into more buckets than the best one in `words` does.
+pub fn lower_bound<const L: usize>(words: &[Word], candidates: &[Word], rules: &Rules) -> usize
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let max_grades = words
+        .iter()
+        .chain(candidates)
+        .map(|&w| worst_and_count::<L>(w, candidates, rules).1)
+        .max()
+        .unwrap_or(1);
+    (1..)
+        .find(|&d| capacity(max_grades, d) >= candidates.len())
+        .unwrap()
+}
+
+impl<'a> Minimax<'a> {
+    pub fn new(words: &'a [Word], width: usize, rules: Rules) -> Minimax<'a> {
+        Minimax {
+            words,
+            width,
+            rules,
+            memo: HashMap::new(),
+        }
+    }
+
+    /// Whether every guess is tried against `candidates` and any subset of them, so a failed
+    /// search proves there is no strategy.
+    fn is_exhaustive(&self, candidates: &[Word]) -> bool {
+        let pool = candidates.iter().chain(self.words).collect::<HashSet<_>>();
+        self.width >= pool.len()
+    }
+
+    /// The guesses to try against `candidates`, most promising first, with their worst buckets.
+    fn guesses<const L: usize>(
+        &self,
+        candidates: &[Word],
+        history: &[(Word, Grade)],
+    ) -> Vec<(Word, usize)>
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        let mut pool = candidates
+            .iter()
+            .chain(self.words)
+            .copied()
+            .collect::<Vec<_>>();
+        // a guess splitting the candidates no better than another is never needed, but hard mode
+        // may forbid the better one later
+        if !self.rules.hard {
+            pool = if candidates.len() <= 16 {
+                undominated::<L>(&pool, candidates, &self.rules)
+            } else {
+                distinct::<L>(&pool, candidates, &self.rules)
+            };
+        }
+        let mut scored = pool
+            .into_iter()
+            .map(|w| (w, candidates.contains(&w)))
+            .filter(|&(w, _)| self.rules.is_legal(w, history))
+            .map(|(w, is_candidate)| {
+                let (worst, count) = worst_and_count::<L>(w, candidates, &self.rules);
+                // then the most buckets, then candidates, which might win straight away
+                (w, (worst, usize::MAX - count, !is_candidate))
+            })
+            .collect::<Vec<_>>();
+        scored.sort_by_key(|&(_, key)| key);
+        let mut seen = HashSet::new();
+        let mut guesses = scored
+            .into_iter()
+            .filter(|&(w, _)| seen.insert(w))
+            .map(|(w, (worst, _, _))| (w, worst))
+            .collect::<Vec<_>>();
+        guesses.truncate(self.width.max(1));
+        guesses
+    }
+
+    /// A strategy finding every one of `candidates` within `depth` guesses, if the search finds
+    /// one. In hard mode, `history` holds the guesses so far with their grades.
+    pub fn solve_within<const L: usize>(
+        &mut self,
+        candidates: &[Word],
+        depth: usize,
+        history: &mut Vec<(Word, Grade)>,
+    ) -> Option<Tree>
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        match (candidates, depth) {
+            ([], _) | (_, 0) => return None,
+            (&[answer], _) => {
+                return Some(Tree {
+                    guess: answer,
+                    branches: Vec::new(),
+                })
+            }
+            (_, 1) => return None,
+            _ => {}
+        }
+        // hard-mode subtrees depend on the path taken, so can't be shared
+        let key = (candidates.to_vec(), depth);
+        if !self.rules.hard {
+            if let Some(tree) = self.memo.get(&key) {
+                return tree.clone();
+            }
+        }
+
+        let max_bucket = capacity(self.rules.n_grades(), depth - 1);
+        let mut found = None;
+        for (guess, worst) in self.guesses::<L>(candidates, history) {
+            if worst == candidates.len() && !candidates.contains(&guess) {
+                // no information, and the guesses are sorted by their worst bucket
+                break;
+            }
+            if worst > max_bucket || (depth == 2 && worst > 1) {
+                break;
+            }
+            let mut buckets = self.rules.bucket::<L>(guess, candidates);
+            let mut branches = Vec::new();
+            let mut ok = true;
+            for (graded, b) in buckets.iter_mut().enumerate() {
+                let graded = graded as Grade;
+                if b.is_empty() || graded == self.rules.all_green() {
+                    continue;
+                }
+                let b = mem::take(b);
+                history.push((guess, graded));
+                let subtree = self.solve_within::<L>(&b, depth - 1, history);
+                history.pop();
+                match subtree {
+                    Some(t) => branches.push((graded, t)),
+                    None => {
+                        ok = false;
+                        break;
+                    }
+                }
+            }
+            if ok {
+                found = Some(Tree { guess, branches });
+                break;
+            }
+        }
+
+        if !self.rules.hard {
+            self.memo.insert(key, found.clone());
+        }
+        found
+    }
+
+    /// The strategy with the fewest guesses in the worst case, trying depths in turn up to
+    /// `max_depth`. Returns the best lower bound proved if there is none within `max_depth`.
+    pub fn solve<const L: usize>(
+        &mut self,
+        candidates: &[Word],
+        max_depth: usize,
+    ) -> Result<Solution, usize>
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        let start = lower_bound::<L>(self.words, candidates, &self.rules);
+        let exhaustive = self.is_exhaustive(candidates);
+        let mut bound = start;
+        for depth in start..=max_depth {
+            match self.solve_within::<L>(candidates, depth, &mut Vec::new()) {
+                Some(tree) => return Ok(self.solution(tree, candidates, bound)),
+                None if exhaustive => bound = depth + 1,
+                None => {}
+            }
+        }
+        Err(bound)
+    }
+
+    /// The best strategy opening with `first`, as for [`Minimax::solve`]. The lower bound is
+    /// then for strategies opening with `first`.
+    pub fn solve_from<const L: usize>(
+        &mut self,
+        first: Word,
+        candidates: &[Word],
+        max_depth: usize,
+    ) -> Result<Solution, usize>
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        let buckets = self.rules.bucket::<L>(first, candidates);
+        let exhaustive = self.is_exhaustive(candidates);
+        let mut branches = Vec::new();
+        let mut bound = 1;
+        for (graded, b) in buckets.iter().enumerate() {
+            let graded = graded as Grade;
+            if b.is_empty() || graded == self.rules.all_green() {
+                continue;
+            }
+            let mut depth = lower_bound::<L>(self.words, b, &self.rules);
+            bound = bound.max(depth + 1);
+            let subtree = loop {
+                if depth + 1 > max_depth {
+                    return Err(bound);
+                }
+                match self.solve_within::<L>(b, depth, &mut vec![(first, graded)]) {
+                    Some(t) => break t,
+                    None if exhaustive => {
+                        depth += 1;
+                        bound = bound.max(depth + 1);
+                    }
+                    None => depth += 1,
+                }
+            };
+            branches.push((graded, subtree));
+        }
+        let tree = Tree {
+            guess: first,
+            branches,
+        };
+        Ok(self.solution(tree, candidates, bound))
+    }
+
+    fn solution(&self, tree: Tree, candidates: &[Word], lower_bound: usize) -> Solution {
+        let depth = tree.depth();
+        let worst = candidates
+            .iter()
+            .copied()
+            .filter(|&a| tree.guesses_for(a, &self.rules) == Some(depth))
+            .collect();
+        Solution {
+            tree,
+            depth,
+            lower_bound: lower_bound.min(depth),
+            worst,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::word_from_str;
+
+    use super::*;
+
+    #[test]
+    fn proves_optimal_depth() {
+        let w = |s: &str| word_from_str(s.as_bytes()).unwrap();
+        let candidates = [
+            "fight", "light", "might", "night", "right", "sight", "tight",
+        ]
+        .map(w);
+        let words = ["flash", "mirth", "sonly"].map(w);
+        let rules = Rules::default();
+
+        // exhaustive over this tiny list, so the bound is proved
+        let mut solver = Minimax::new(&words, usize::MAX, rules);
+        let solution = solver.solve::<4>(&candidates, 6).unwrap();
+        assert_eq!(solution.depth, solution.lower_bound);
+        for &c in &candidates {
+            assert!(solution.tree.guesses_for(c, &rules).unwrap() <= solution.depth);
+        }
+        assert!(solution
+            .worst
+            .iter()
+            .all(|&c| solution.tree.guesses_for(c, &rules) == Some(solution.depth)));
+        assert_eq!(solver.solve::<4>(&candidates, 2), Err(3));
+        // as wide as the word list, but the candidates are guesses too
+        let n_guesses = words.len() + candidates.len();
+        assert!(!Minimax::new(&words, words.len(), rules).is_exhaustive(&candidates));
+        assert!(Minimax::new(&words, n_guesses, rules).is_exhaustive(&candidates));
+
+        let hard = Rules {
+            hard: true,
+            ..rules
+        };
+        let hard = Minimax::new(&words, usize::MAX, hard)
+            .solve::<4>(&candidates, 7)
+            .unwrap();
+        // in hard mode the letters revealed by `flash` etc. can't be dropped
+        assert!(hard.depth >= solution.depth);
+
+        // four letters, graded as such
+        let four = Rules {
+            word_length: 4,
+            ..rules
+        };
+        let w = |s: &str| four.word_from_str(s.as_bytes()).unwrap();
+        let candidates = ["figh", "ligh", "migh", "nigh", "righ", "sigh", "tigh"].map(w);
+        let words = ["flas", "mirt", "sonl"].map(w);
+        let short = Minimax::new(&words, usize::MAX, four)
+            .solve::<4>(&candidates, 6)
+            .unwrap();
+        assert_eq!(short.depth, short.lower_bound);
+        for &c in &candidates {
+            assert!(short.tree.guesses_for(c, &four).unwrap() <= short.depth);
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use std::simd::{LaneCount, SupportedLaneCount};
+
+use crate::{
+    squeeze::{entropy_after, filter},
+    Grade, Word, ALL_GREEN,
+};
+
+/// One of several boards being played at once, as in Dordle, Quordle or Octordle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Board {
+    /// The answers consistent with every grade seen on this board.
+    pub candidates: Vec<Word>,
+    pub solved: bool,
+}
+
+/// A game where every guess is graded against several hidden answers at once.
+#[derive(Clone, Debug)]
+pub struct Game {
+    boards: Vec<Board>,
+    guesses: Vec<Word>,
+}
+
+impl Game {
+    pub fn new(n_boards: usize, answers: &[Word]) -> Game {
+        Game {
+            boards: vec![
+                Board {
+                    candidates: answers.to_vec(),
+                    solved: false,
+                };
+                n_boards
+            ],
+            guesses: Vec::new(),
+        }
+    }
+
+    pub fn boards(&self) -> &[Board] {
+        &self.boards
+    }
+
+    pub fn guesses(&self) -> &[Word] {
+        &self.guesses
+    }
+
+    pub fn is_won(&self) -> bool {
+        self.boards.iter().all(|b| b.solved)
+    }
+
+    /// Record `guess` being graded `grades[i]` on the `i`-th board.
+    /// Grades given for boards which are already solved are ignored.
+    pub fn update<const L: usize>(&mut self, guess: Word, grades: &[Grade])
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        assert_eq!(grades.len(), self.boards.len());
+        self.guesses.push(guess);
+        for (board, &graded) in self.boards.iter_mut().zip(grades) {
+            if board.solved {
+                continue;
+            }
+            if graded == ALL_GREEN {
+                board.solved = true;
+                board.candidates = vec![guess];
+            } else {
+                board.candidates = filter::<L>(guess, graded, &board.candidates);
+            }
+        }
+    }
+
+    /// Score `guess` by the total entropy it leaves across unsolved boards, less one bit for
+    /// every board it is expected to solve outright. Lower is better.
+    pub fn score<const L: usize>(&self, guess: Word) -> f32
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        self.boards
+            .iter()
+            .filter(|b| !b.solved && !b.candidates.is_empty())
+            .map(|b| {
+                let p_solve = if b.candidates.contains(&guess) {
+                    1.0 / b.candidates.len() as f32
+                } else {
+                    0.0
+                };
+                entropy_after::<L>(guess, &b.candidates) - p_solve
+            })
+            .sum()
+    }
+
+    /// Pick the next guess. A board with only one candidate left is always finished first;
+    /// otherwise the best-scoring word wins, with ties going to words which could be an answer.
+    /// Returns `None` if some unsolved board has no candidates left.
+    pub fn suggest<const L: usize>(&self, words: &[Word]) -> Option<Word>
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        let unsolved = self.boards.iter().filter(|b| !b.solved);
+        if unsolved.clone().any(|b| b.candidates.is_empty()) {
+            return None;
+        }
+        if let Some(b) = unsolved.clone().find(|b| b.candidates.len() == 1) {
+            return Some(b.candidates[0]);
+        }
+        unsolved
+            .flat_map(|b| &b.candidates)
+            .chain(words)
+            .map(|&w| (w, self.score::<L>(w)))
+            .reduce(|best, next| if next.1 < best.1 { next } else { best })
+            .map(|(w, _)| w)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{grade_from_str, squeeze::grade, word_from_str};
+
+    use super::*;
+
+    #[test]
+    fn quordle_finishes() {
+        let answers = [
+            "cigar", "rebut", "sissy", "humph", "awake", "blush", "focal", "evade", "naval",
+            "serve", "heath", "dwarf", "model", "karma", "stink", "grade",
+        ]
+        .map(|s| word_from_str(s.as_bytes()).unwrap());
+        let words = ["trace", "lions"].map(|s| word_from_str(s.as_bytes()).unwrap());
+        let hidden = [answers[3], answers[7], answers[11], answers[15]];
+
+        let mut game = Game::new(hidden.len(), &answers);
+        while !game.is_won() {
+            assert!(game.guesses().len() < 20);
+            let guess = game.suggest::<4>(&words).unwrap();
+            let grades = hidden.map(|h| grade(guess, h));
+            game.update::<4>(guess, &grades);
+            for (b, &h) in game.boards().iter().zip(&hidden) {
+                assert!(b.candidates.contains(&h));
+            }
+        }
+        for h in hidden {
+            assert!(game.guesses().contains(&h));
+        }
+
+        // no answer but cigar itself starts with "ciga"
+        let mut game = Game::new(2, &answers);
+        let nearly = grade_from_str(b"ggggb").unwrap();
+        game.update::<4>(answers[0], &[nearly, grade(answers[0], answers[1])]);
+        assert_eq!(game.suggest::<4>(&words), None);
+    }
+}
@@ -0,0 +1,359 @@
+use std::{
+    collections::{BinaryHeap, HashSet},
+    simd::{LaneCount, SupportedLaneCount},
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+    thread::scope,
+};
+
+use crate::{equiv::distinct, partition::Partition, rules::Rules, Word};
+
+/// Every word with the entropy left after opening with it alone under `rules`, best first.
+pub fn rank_words<const L: usize>(
+    words: &[Word],
+    answers: &[Word],
+    rules: &Rules,
+) -> Vec<(Word, f32)>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let mut ranked = words
+        .iter()
+        .map(|&w| (w, rules.entropy_after::<L>(w, answers)))
+        .collect::<Vec<_>>();
+    ranked.sort_unstable_by(|&(_, e1), &(_, e2)| e1.partial_cmp(&e2).unwrap());
+    ranked
+}
+
+/// Find the `top_k` sets of `n_words` distinct openers leaving the least entropy, best first.
+/// Only the `pool` best words as lone openers are considered, and of those splitting the answers
+/// the same way only the best ranked, so no two sets found differ only by equivalent words.
+///
+/// Entropies are shared between threads as fixed-point `u64`s with 7 decimal places, and sets
+/// are pruned once the information the remaining words could add on their own is not enough to
+/// beat the `top_k`-th best set found so far.
+pub fn search<const L: usize>(
+    n_words: usize,
+    words: &[Word],
+    answers: &[Word],
+    pool: usize,
+    top_k: usize,
+    n_threads: usize,
+    rules: &Rules,
+) -> Vec<(Vec<Word>, f32)>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    search_below::<L>(
+        n_words,
+        words,
+        answers,
+        pool,
+        Some(top_k),
+        n_threads,
+        u64::MAX,
+        rules,
+    )
+    .0
+    .into_iter()
+    .map(|(ent, set)| (set, ent as f32 / 1e7))
+    .collect()
+}
+
+/// Statistics on the answers left after playing a fixed sequence of openers.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Evaluation {
+    /// Expected bits left to identify the answer.
+    pub entropy: f32,
+    /// Expected number of answers still possible.
+    pub expected_candidates: f32,
+    /// Number of answers still possible in the worst case.
+    pub worst_bucket: usize,
+    /// Number of answers which are known for certain.
+    pub solved: usize,
+}
+
+pub fn evaluate<const L: usize>(openers: &[Word], answers: &[Word], rules: &Rules) -> Evaluation
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let partition = openers
+        .iter()
+        .fold(Partition::new(answers, *rules), |p, &w| p.refine::<L>(w));
+    let classes = partition.classes();
+    Evaluation {
+        entropy: partition.entropy(),
+        expected_candidates: classes
+            .iter()
+            .map(|c| (c.len() * c.len()) as f32)
+            .sum::<f32>()
+            / answers.len() as f32,
+        worst_bucket: classes.iter().map(Vec::len).max().unwrap_or(0),
+        solved: classes.iter().filter(|c| c.len() == 1).count(),
+    }
+}
+
+/// Entropies closer than this, in fixed point, are counted as equal by [`rank`], since the
+/// same set can come out slightly differently depending on the order of summation.
+const TIE: u64 = 1000;
+
+/// The 1-indexed rank of `openers` among all sets of as many distinct words from `words`.
+/// Every set counts, including those differing only by words which split the answers the same
+/// way, which [`search`] would leave out. Sets within [`TIE`] of each other share a rank.
+pub fn rank<const L: usize>(
+    openers: &[Word],
+    words: &[Word],
+    answers: &[Word],
+    n_threads: usize,
+    rules: &Rules,
+) -> usize
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let key = (evaluate::<L>(openers, answers, rules).entropy * 1e7) as u64;
+    let (_, better) = search_below::<L>(
+        openers.len(),
+        words,
+        answers,
+        words.len(),
+        None,
+        n_threads,
+        key.saturating_sub(TIE),
+        rules,
+    );
+    better + 1
+}
+
+/// Find the `top_k` best sets as in [`search`], ignoring any whose fixed-point entropy is not
+/// below `threshold`. With no `top_k`, count every set below `threshold` instead of keeping any,
+/// equivalent words included.
+#[allow(clippy::too_many_arguments)]
+fn search_below<const L: usize>(
+    n_words: usize,
+    words: &[Word],
+    answers: &[Word],
+    pool: usize,
+    top_k: Option<usize>,
+    n_threads: usize,
+    threshold: u64,
+    rules: &Rules,
+) -> (Vec<(u64, Vec<Word>)>, usize)
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let mut ranked = rank_words::<L>(words, answers, rules);
+    ranked.truncate(pool);
+    if top_k.is_some() {
+        // sets differing only by words which split the answers the same way do equally well,
+        // so only the best ranked of each is kept; counting needs them all
+        let words = ranked.iter().map(|&(w, _)| w).collect::<Vec<_>>();
+        let distinct = distinct::<L>(&words, answers, rules)
+            .into_iter()
+            .collect::<HashSet<_>>();
+        ranked.retain(|(w, _)| distinct.contains(w));
+    }
+    if n_words == 0 || n_words > ranked.len() || top_k == Some(0) {
+        return (Vec::new(), 0);
+    }
+    if n_words == 1 {
+        let below = ranked
+            .into_iter()
+            .map(|(w, e)| ((e * 1e7) as u64, vec![w]))
+            .filter(|&(k, _)| k < threshold)
+            .collect::<Vec<_>>();
+        let count = below.len();
+        return (below.into_iter().take(top_k.unwrap_or(0)).collect(), count);
+    }
+
+    let initial_entropy = (answers.len() as f32).log2();
+    let gains = ranked
+        .iter()
+        .map(|&(_, e)| initial_entropy - e)
+        .collect::<Vec<_>>();
+    // best_gains[r] is the most information r words could give if they were independent
+    let best_gains = (0..n_words)
+        .map(|r| gains[..r].iter().sum::<f32>())
+        .collect::<Vec<_>>();
+
+    let threshold = AtomicU64::new(threshold);
+    let next_start = AtomicUsize::new(n_words - 1);
+    let root = Partition::new(answers, *rules);
+
+    let do_work = || {
+        let mut searcher = Searcher {
+            ranked: &ranked,
+            gains: &gains,
+            best_gains: &best_gains,
+            threshold: &threshold,
+            top_k,
+            chosen: Vec::with_capacity(n_words),
+            best: BinaryHeap::new(),
+            count: 0,
+        };
+        loop {
+            let i = next_start.fetch_add(1, Ordering::Relaxed);
+            if i >= ranked.len() {
+                break;
+            }
+            let (w0, el0) = ranked[i];
+            if el0 - best_gains[n_words - 1] >= searcher.threshold() {
+                // we sorted the words, so we won't have any more anyway
+                break;
+            }
+            searcher.chosen.push(w0);
+            searcher.extend::<L>(&root.refine::<L>(w0), i, n_words - 1);
+            searcher.chosen.pop();
+        }
+        (searcher.best.into_vec(), searcher.count)
+    };
+
+    let (mut found, mut count) = (Vec::new(), 0);
+    scope(|s| {
+        let handles = (0..n_threads.max(1))
+            .map(|_| s.spawn(do_work))
+            .collect::<Vec<_>>();
+        for handle in handles {
+            let (best, n) = handle.join().unwrap();
+            found.extend(best);
+            count += n;
+        }
+    });
+
+    found.sort_unstable();
+    found.truncate(top_k.unwrap_or(0));
+    (found, count)
+}
+
+struct Searcher<'a> {
+    ranked: &'a [(Word, f32)],
+    gains: &'a [f32],
+    best_gains: &'a [f32],
+    threshold: &'a AtomicU64,
+    top_k: Option<usize>,
+    chosen: Vec<Word>,
+    /// Max-heap of the best sets found by this thread, keyed by fixed-point entropy.
+    best: BinaryHeap<(u64, Vec<Word>)>,
+    /// How many sets this thread found below the threshold, when only counting.
+    count: usize,
+}
+
+impl Searcher<'_> {
+    fn threshold(&self) -> f32 {
+        self.threshold.load(Ordering::Relaxed) as f32 / 1e7
+    }
+
+    /// Add `slots` more words, each ranked strictly better than the `below`-th word.
+    fn extend<const L: usize>(&mut self, partition: &Partition, below: usize, slots: usize)
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        let current = partition.entropy();
+        for j in slots - 1..below {
+            // later words have less gain, so the bound only gets worse
+            if current - self.gains[j] - self.best_gains[slots - 1] >= self.threshold() {
+                break;
+            }
+            let w = self.ranked[j].0;
+            if slots == 1 {
+                self.record(partition.entropy_after::<L>(w), w);
+            } else {
+                self.chosen.push(w);
+                self.extend::<L>(&partition.refine::<L>(w), j, slots - 1);
+                self.chosen.pop();
+            }
+        }
+    }
+
+    fn record(&mut self, entropy: f32, last: Word) {
+        let key = (entropy * 1e7) as u64;
+        let Some(top_k) = self.top_k else {
+            // the threshold never moves when counting
+            if key < self.threshold.load(Ordering::Relaxed) {
+                self.count += 1;
+            }
+            return;
+        };
+        if self.best.len() == top_k && self.best.peek().is_some_and(|&(k, _)| k <= key) {
+            return;
+        }
+        let mut set = self.chosen.clone();
+        set.push(last);
+        self.best.push((key, set));
+        if self.best.len() > top_k {
+            self.best.pop();
+        }
+        if self.best.len() == top_k {
+            self.threshold
+                .fetch_min(self.best.peek().unwrap().0, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::word_from_str;
+
+    use super::*;
+
+    #[test]
+    fn evaluate_counts_classes() {
+        let answers = ["cigar", "rebut", "sissy", "humph", "awake", "blush"]
+            .map(|s| word_from_str(s.as_bytes()).unwrap());
+        let trace = word_from_str(b"trace").unwrap();
+
+        let rules = Rules::default();
+        let p = Partition::new(&answers, rules).refine::<4>(trace);
+        let eval = evaluate::<4>(&[trace], &answers, &rules);
+        assert_eq!(eval.entropy, p.entropy());
+        assert_eq!(
+            eval.worst_bucket,
+            p.classes().iter().map(Vec::len).max().unwrap()
+        );
+        assert_eq!(
+            eval.solved,
+            p.classes().iter().filter(|c| c.len() == 1).count()
+        );
+        assert!(eval.expected_candidates >= 1.0);
+    }
+
+    #[test]
+    fn pairs_match_brute_force() {
+        let answers = [
+            "cigar", "rebut", "sissy", "humph", "awake", "blush", "focal", "evade", "naval",
+            "serve", "heath", "dwarf",
+        ]
+        .map(|s| word_from_str(s.as_bytes()).unwrap());
+        let words = [
+            "trace", "lions", "salet", "crane", "humph", "fuzzy", "qajaq", "blush", "roate",
+        ]
+        .map(|s| word_from_str(s.as_bytes()).unwrap());
+
+        let rules = Rules::default();
+        let mut brute = Vec::new();
+        for i in 0..words.len() {
+            for j in 0..i {
+                let p = Partition::new(&answers, rules)
+                    .refine::<4>(words[i])
+                    .refine::<4>(words[j]);
+                brute.push(p.entropy());
+            }
+        }
+        brute.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let found = search::<4>(2, &words, &answers, words.len(), 5, 2, &rules);
+        assert_eq!(found.len(), 5);
+        for ((set, ent), &expected) in found.iter().zip(&brute) {
+            assert_eq!(set.len(), 2);
+            assert!((ent - expected).abs() < 1e-5, "{ent} != {expected}");
+        }
+
+        let (best_set, _) = &found[0];
+        assert_eq!(rank::<4>(best_set, &words, &answers, 2, &rules), 1);
+        let (best_word, _) = rank_words::<4>(&words, &answers, &rules)[0];
+        assert_eq!(rank::<4>(&[best_word], &words, &answers, 2, &rules), 1);
+        let (fifth_set, fifth_ent) = &found[4];
+        assert_eq!(
+            rank::<4>(fifth_set, &words, &answers, 2, &rules),
+            brute.iter().filter(|&&e| e < fifth_ent - 1e-6).count() + 1
+        );
+    }
+}
@@ -1,15 +1,25 @@
-use crate::{Grade, Word, BLACK, GREEN, YELLOW};
+use crate::{
+    policy::{Anywhere, DupPolicy, Policy},
+    Grade, Word, BLACK, GREEN, YELLOW,
+};
 
-pub fn grade(guess: Word, soln: Word) -> Grade {
+/// The greens of `guess` against `soln`, and the bank of letters of `soln` left to turn the rest
+/// yellow: three bits counting each letter, `a` lowest.
+pub(crate) fn greens_and_bank(guess: Word, soln: Word) -> (Grade, u128) {
+    greens_and_bank_n::<5>(guess, soln)
+}
+
+/// [`greens_and_bank`] for words of `N` letters, up to 6.
+pub(crate) fn greens_and_bank_n<const N: usize>(guess: Word, soln: Word) -> (Grade, u128) {
     let mut yellow_bank = 0u128;
     let mut grade = 0u16;
     let mut guess2 = guess;
     let mut soln2 = soln;
-    for _ in 0..5 {
+    for _ in 0..N {
         let matches_bottom_5 = (guess2 ^ soln2) & 0x1f == 0;
 
         if matches_bottom_5 {
-            grade |= GREEN << 10;
+            grade |= GREEN << (2 * N);
         } else {
             let sc = soln2 & 0x1f;
             yellow_bank += 1 << (3 * sc);
@@ -18,8 +28,17 @@ pub fn grade(guess: Word, soln: Word) -> Grade {
         guess2 >>= 5;
         soln2 >>= 5;
     }
+    (grade, yellow_bank)
+}
+
+pub fn grade(guess: Word, soln: Word) -> Grade {
+    grade_n::<5>(guess, soln)
+}
 
-    for i in 0..5 {
+/// [`grade`] for words of `N` letters, up to 6.
+pub fn grade_n<const N: usize>(guess: Word, soln: Word) -> Grade {
+    let (mut grade, mut yellow_bank) = greens_and_bank_n::<N>(guess, soln);
+    for i in 0..N {
         let c = (guess >> (5 * i)) & 0x1f;
         if grade & (0b11 << (2 * i)) == BLACK {
             let nyellow = (yellow_bank >> (3 * c)) & 0b111;
@@ -33,6 +52,13 @@ pub fn grade(guess: Word, soln: Word) -> Grade {
     grade
 }
 
+pub fn grade_with(guess: Word, soln: Word, policy: DupPolicy) -> Grade {
+    match policy {
+        DupPolicy::Nyt => grade(guess, soln),
+        DupPolicy::Anywhere => Anywhere::grade(guess, soln),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::word_from_str;
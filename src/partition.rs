@@ -0,0 +1,127 @@
+use std::simd::{LaneCount, Simd, SupportedLaneCount};
+
+use crate::{
+    rules::Rules,
+    squeeze::{grade, gradel},
+    Word, N_GRADES,
+};
+
+/// Push every solution in `solns` onto the bucket for the grade `guess` gets against it.
+pub fn bucket<const L: usize>(guess: Word, solns: &[Word], buckets: &mut [Vec<Word>; N_GRADES])
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let (prefix, simds, suffix) = solns.as_simd::<L>();
+    for &answer in prefix {
+        buckets[grade(guess, answer) as usize].push(answer);
+    }
+    for &answer in suffix {
+        buckets[grade(guess, answer) as usize].push(answer);
+    }
+    for &answer in simds {
+        let grades = gradel(Simd::splat(guess), answer);
+        for (graded, answer) in grades.to_array().into_iter().zip(answer.to_array()) {
+            buckets[graded as usize].push(answer);
+        }
+    }
+}
+
+/// The classes of answers which cannot be told apart after some sequence of guesses under
+/// `rules`.
+#[derive(Clone, Debug)]
+pub struct Partition {
+    classes: Vec<Vec<Word>>,
+    n_answers: usize,
+    rules: Rules,
+}
+
+impl Partition {
+    pub fn new(answers: &[Word], rules: Rules) -> Partition {
+        Partition {
+            classes: if answers.is_empty() {
+                Vec::new()
+            } else {
+                vec![answers.to_vec()]
+            },
+            n_answers: answers.len(),
+            rules,
+        }
+    }
+
+    pub fn classes(&self) -> &[Vec<Word>] {
+        &self.classes
+    }
+
+    pub fn n_answers(&self) -> usize {
+        self.n_answers
+    }
+
+    /// Split every class by the grade `guess` receives.
+    pub fn refine<const L: usize>(&self, guess: Word) -> Partition
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        let mut classes = Vec::with_capacity(self.classes.len());
+        for class in &self.classes {
+            if class.len() == 1 {
+                classes.push(class.clone());
+                continue;
+            }
+            let buckets = self.rules.bucket::<L>(guess, class);
+            classes.extend(buckets.into_iter().filter(|b| !b.is_empty()));
+        }
+        Partition {
+            classes,
+            n_answers: self.n_answers,
+            rules: self.rules,
+        }
+    }
+
+    /// Expected bits left to identify the answer.
+    pub fn entropy(&self) -> f32 {
+        self.classes
+            .iter()
+            .filter(|c| c.len() > 1)
+            .map(|c| (c.len() as f32).log2() * c.len() as f32)
+            .sum::<f32>()
+            / self.n_answers as f32
+    }
+
+    /// Equivalent to `self.refine(guess).entropy()`, without building the refinement.
+    pub fn entropy_after<const L: usize>(&self, guess: Word) -> f32
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        self.classes
+            .iter()
+            .filter(|c| c.len() > 1)
+            .map(|c| self.rules.entropy_after::<L>(guess, c) * c.len() as f32)
+            .sum::<f32>()
+            / self.n_answers as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{squeeze::entropy_after, word_from_str};
+
+    use super::*;
+
+    #[test]
+    fn refine_matches_entropy_after() {
+        let answers = [
+            "cigar", "rebut", "sissy", "humph", "awake", "blush", "focal", "evade",
+        ]
+        .map(|s| word_from_str(s.as_bytes()).unwrap());
+        let trace = word_from_str(b"trace").unwrap();
+        let lions = word_from_str(b"lions").unwrap();
+
+        let p = Partition::new(&answers, Rules::default()).refine::<4>(trace);
+        assert_eq!(
+            p.classes().iter().map(Vec::len).sum::<usize>(),
+            answers.len()
+        );
+        assert!((p.entropy() - entropy_after::<4>(trace, &answers)).abs() < 1e-5);
+        assert!((p.refine::<4>(lions).entropy() - p.entropy_after::<4>(lions)).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,112 @@
+use std::simd::{
+    cmp::{SimdPartialEq, SimdPartialOrd},
+    LaneCount, Simd, SupportedLaneCount,
+};
+
+use crate::{scoring::Scorer, Grade, Word, N_GRADES};
+
+/// The hidden letter is the one guessed.
+pub const SAME: u16 = 0b10;
+/// The hidden letter comes earlier in the alphabet than the one guessed.
+pub const EARLIER: u16 = 0b01;
+/// The hidden letter comes later in the alphabet than the one guessed.
+pub const LATER: u16 = 0b00;
+
+/// The feedback for each position of `guess`, packed two bits per position like a grade.
+pub fn grade(guess: Word, soln: Word) -> Grade {
+    let mut grade = 0;
+    for i in 0..5 {
+        let gc = (guess >> (5 * i)) & 0x1f;
+        let sc = (soln >> (5 * i)) & 0x1f;
+        grade |= if sc == gc {
+            SAME
+        } else if sc < gc {
+            EARLIER
+        } else {
+            LATER
+        } << (2 * i);
+    }
+    grade
+}
+
+pub fn gradel<const L: usize>(words: Simd<Word, L>, solns: Simd<Word, L>) -> Simd<u32, L>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let mut grade = Simd::splat(0);
+    for i in 0..5 {
+        let shift = Simd::splat(5 * i);
+        let gc = (words >> shift) & Simd::splat(0x1f);
+        let sc = (solns >> shift) & Simd::splat(0x1f);
+        grade |= sc
+            .simd_eq(gc)
+            .select(Simd::splat((SAME as u32) << (2 * i)), Simd::splat(0));
+        grade |= sc
+            .simd_lt(gc)
+            .select(Simd::splat((EARLIER as u32) << (2 * i)), Simd::splat(0));
+    }
+    grade
+}
+
+/// Peaks, where each position says which way the hidden letter lies in the alphabet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Peaks;
+
+impl Scorer for Peaks {
+    fn n_feedbacks(&self) -> usize {
+        N_GRADES
+    }
+
+    fn score(&self, guess: Word, soln: Word) -> usize {
+        grade(guess, soln).into()
+    }
+
+    fn scorel<const L: usize>(&self, guesses: Simd<Word, L>, solns: Simd<Word, L>) -> [usize; L]
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        gradel(guesses, solns).to_array().map(|g| g as usize)
+    }
+}
+
+/// Parse feedback written as five of `=` (same letter), `<` (earlier) and `>` (later).
+pub fn grade_from_str(s: &[u8]) -> Option<Grade> {
+    if s.len() != 5 {
+        return None;
+    }
+    let mut g = 0;
+    for (i, &c) in s.iter().enumerate() {
+        g |= match c {
+            b'=' => SAME,
+            b'<' => EARLIER,
+            b'>' => LATER,
+            _ => return None,
+        } << (2 * i);
+    }
+    Some(g)
+}
+
+pub fn str_from_grade(grade: Grade) -> [u8; 5] {
+    std::array::from_fn(|i| match (grade >> (2 * i)) & 0b11 {
+        SAME => b'=',
+        EARLIER => b'<',
+        _ => b'>',
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::word_from_str;
+
+    use super::*;
+
+    #[test]
+    fn directions() {
+        let graded = grade(
+            word_from_str(b"horse").unwrap(),
+            word_from_str(b"crane").unwrap(),
+        );
+        assert_eq!(&str_from_grade(graded), b"<><<=");
+        assert_eq!(grade_from_str(b"<><<="), Some(graded));
+    }
+}
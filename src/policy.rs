@@ -0,0 +1,135 @@
+use std::{
+    simd::{LaneCount, Simd, SupportedLaneCount},
+    str::FromStr,
+};
+
+use crate::{squeeze, Grade, Word};
+
+/// A rule for grading letters which appear in the guess more often than in the answer.
+pub trait Policy {
+    fn grade(guess: Word, soln: Word) -> Grade;
+
+    fn gradel<const L: usize>(words: Simd<Word, L>, solns: Simd<Word, L>) -> Simd<u32, L>
+    where
+        LaneCount<L>: SupportedLaneCount;
+}
+
+/// The New York Times rule: each letter of the answer not already green can turn at most one
+/// letter of the guess yellow, from left to right.
+pub struct Nyt;
+
+/// Every letter of the guess which appears anywhere in the answer is at least yellow.
+pub struct Anywhere;
+
+impl Policy for Nyt {
+    fn grade(guess: Word, soln: Word) -> Grade {
+        squeeze::grade(guess, soln)
+    }
+
+    fn gradel<const L: usize>(words: Simd<Word, L>, solns: Simd<Word, L>) -> Simd<u32, L>
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        squeeze::gradel(words, solns)
+    }
+}
+
+impl Policy for Anywhere {
+    fn grade(guess: Word, soln: Word) -> Grade {
+        squeeze::grade_anywhere(guess, soln)
+    }
+
+    fn gradel<const L: usize>(words: Simd<Word, L>, solns: Simd<Word, L>) -> Simd<u32, L>
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        squeeze::gradel_anywhere(words, solns)
+    }
+}
+
+/// A [`Policy`] chosen at runtime. It is followed by its own methods, the `_with` grading
+/// helpers and [`Rules`](crate::rules::Rules); `partition`, `opener`, `hard`, `followup` and
+/// `multi` always grade the New York Times way.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DupPolicy {
+    #[default]
+    Nyt,
+    Anywhere,
+}
+
+impl DupPolicy {
+    pub fn grade(self, guess: Word, soln: Word) -> Grade {
+        match self {
+            DupPolicy::Nyt => Nyt::grade(guess, soln),
+            DupPolicy::Anywhere => Anywhere::grade(guess, soln),
+        }
+    }
+
+    pub fn filter<const L: usize>(self, guess: Word, graded: Grade, solns: &[Word]) -> Vec<Word>
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        match self {
+            DupPolicy::Nyt => squeeze::filter_with::<Nyt, L>(guess, graded, solns),
+            DupPolicy::Anywhere => squeeze::filter_with::<Anywhere, L>(guess, graded, solns),
+        }
+    }
+
+    pub fn entropy_after<const L: usize>(self, word: Word, solns: &[Word]) -> f32
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        match self {
+            DupPolicy::Nyt => squeeze::entropy_after_with::<Nyt, L>(word, solns),
+            DupPolicy::Anywhere => squeeze::entropy_after_with::<Anywhere, L>(word, solns),
+        }
+    }
+}
+
+impl FromStr for DupPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "nyt" => Ok(DupPolicy::Nyt),
+            "anywhere" => Ok(DupPolicy::Anywhere),
+            _ => Err(format!(
+                "unknown duplicate-letter policy {s:?}; expected nyt or anywhere"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{packed, sensible, word_from_str};
+
+    use super::*;
+
+    #[test]
+    fn paths_agree() {
+        let words = [
+            "speed", "abide", "eerie", "geese", "roses", "horse", "aahed", "cigar", "llama",
+        ];
+        for guess in words {
+            for soln in words {
+                let w = |s: &str| word_from_str(s.as_bytes()).unwrap();
+                let packed = packed::grade_with(w(guess), w(soln), DupPolicy::Anywhere);
+                assert_eq!(packed, Anywhere::grade(w(guess), w(soln)));
+                assert_eq!(
+                    Anywhere::gradel::<1>(Simd::splat(w(guess)), Simd::splat(w(soln)))[0] as Grade,
+                    packed
+                );
+
+                let sensible = sensible::grade_with(
+                    guess.as_bytes().try_into().unwrap(),
+                    soln.as_bytes().try_into().unwrap(),
+                    DupPolicy::Anywhere,
+                );
+                for (i, color) in sensible.into_iter().enumerate() {
+                    assert_eq!(color as Grade, (packed >> (2 * i)) & 0b11);
+                }
+            }
+        }
+    }
+}
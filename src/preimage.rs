@@ -0,0 +1,156 @@
+use std::{
+    collections::HashSet,
+    simd::{cmp::SimdPartialEq, LaneCount, Simd, SupportedLaneCount},
+};
+
+use crate::{hard::Hints, squeeze, Grade, Word, ALL_GREEN};
+
+/// The grade each of `words` gets if `soln` is the answer.
+pub fn grades<const L: usize>(words: &[Word], soln: Word) -> Vec<Grade>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let mut grades = Vec::with_capacity(words.len());
+    let (prefix, simds, suffix) = words.as_simd::<L>();
+    grades.extend(prefix.iter().map(|&w| squeeze::grade(w, soln)));
+    for &chunk in simds {
+        let graded = squeeze::gradel(chunk, Simd::splat(soln));
+        grades.extend(graded.to_array().map(|g| g as Grade));
+    }
+    grades.extend(suffix.iter().map(|&w| squeeze::grade(w, soln)));
+    grades
+}
+
+/// Every word in `words` which gets the grade `graded` if `soln` is the answer.
+pub fn preimage<const L: usize>(soln: Word, graded: Grade, words: &[Word]) -> Vec<Word>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let mut found = Vec::new();
+    let (prefix, simds, suffix) = words.as_simd::<L>();
+    found.extend(
+        prefix
+            .iter()
+            .filter(|&&w| squeeze::grade(w, soln) == graded),
+    );
+    for &chunk in simds {
+        let matches = squeeze::gradel(chunk, Simd::splat(soln)).simd_eq(Simd::splat(graded.into()));
+        for (m, w) in matches.to_array().into_iter().zip(chunk.to_array()) {
+            if m {
+                found.push(w);
+            }
+        }
+    }
+    found.extend(
+        suffix
+            .iter()
+            .filter(|&&w| squeeze::grade(w, soln) == graded),
+    );
+    found
+}
+
+/// For each row of a grid, the words in `words` which could have been guessed there if `soln`
+/// is the answer. In hard mode a word only counts if it fits into a whole sequence of legal
+/// guesses giving every row. An all-green row is always the answer itself.
+pub fn solve_grid<const L: usize>(
+    rows: &[Grade],
+    soln: Word,
+    words: &[Word],
+    hard: bool,
+) -> Vec<Vec<Word>>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let options = rows
+        .iter()
+        .map(|&row| {
+            if row == ALL_GREEN {
+                vec![soln]
+            } else {
+                preimage::<L>(soln, row, words)
+            }
+        })
+        .collect::<Vec<_>>();
+    if !hard {
+        return options;
+    }
+
+    // the hints reachable before each row, then those from which the rest can be finished
+    let mut reachable = vec![HashSet::from([Hints::default()])];
+    for (&row, opts) in rows.iter().zip(&options) {
+        let next = reachable
+            .last()
+            .unwrap()
+            .iter()
+            .flat_map(|h| {
+                opts.iter()
+                    .filter(|&&w| h.allows(w))
+                    .map(move |&w| h.after(w, row))
+            })
+            .collect();
+        reachable.push(next);
+    }
+    let mut finishing = reachable.pop().unwrap();
+    let mut viable = vec![Vec::new(); rows.len()];
+    for (r, before) in reachable.into_iter().enumerate().rev() {
+        let mut kept = HashSet::new();
+        let mut words = HashSet::new();
+        for h in before {
+            for &w in &options[r] {
+                if h.allows(w) && finishing.contains(&h.after(w, rows[r])) {
+                    kept.insert(h);
+                    words.insert(w);
+                }
+            }
+        }
+        viable[r] = options[r]
+            .iter()
+            .copied()
+            .filter(|w| words.contains(w))
+            .collect();
+        finishing = kept;
+    }
+    viable
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{grade_from_str, word_from_str};
+
+    use super::*;
+
+    #[test]
+    fn inverts_grade() {
+        let words = [
+            "crate", "trace", "caret", "lions", "cruel", "react", "xylyl",
+        ]
+        .map(|s| word_from_str(s.as_bytes()).unwrap());
+        let soln = words[0];
+        for graded in grades::<4>(&words, soln) {
+            let found = preimage::<4>(soln, graded, &words);
+            assert!(!found.is_empty());
+            assert!(found.iter().all(|&w| squeeze::grade(w, soln) == graded));
+        }
+        assert_eq!(
+            preimage::<4>(soln, grade_from_str(b"bbbbb").unwrap(), &words),
+            [words[3], words[6]]
+        );
+    }
+
+    #[test]
+    fn hard_grid_needs_whole_sequence() {
+        let w = |s: &str| word_from_str(s.as_bytes()).unwrap();
+        let words = ["crate", "trace", "lions", "cruel", "xylyl"].map(w);
+        let soln = w("crate");
+        let rows = [
+            squeeze::grade(w("cruel"), soln),
+            grade_from_str(b"bbbbb").unwrap(),
+            ALL_GREEN,
+        ];
+        let easy = solve_grid::<4>(&rows, soln, &words, false);
+        assert_eq!(easy[1], [w("lions"), w("xylyl")]);
+        // both blank guesses drop the c, r and e revealed by `cruel`
+        let hard = solve_grid::<4>(&rows, soln, &words, true);
+        assert!(hard.iter().all(Vec::is_empty));
+    }
+}
@@ -0,0 +1,202 @@
+use std::{
+    simd::{
+        cmp::{SimdPartialEq, SimdPartialOrd},
+        LaneCount, Mask, Simd, SupportedLaneCount,
+    },
+    str::FromStr,
+};
+
+use crate::Word;
+
+const ANY: u32 = (1 << 26) - 1;
+
+/// A search over words, written as whitespace- or comma-separated terms:
+///
+/// - `c?a?e`: a letter or `?` (also `.`) for each position
+/// - `+rs`: contains each of these letters, repeating one to require it more than once
+/// - `-st`: contains none of these letters
+/// - `e>=2`, `e<=1`, `e=1`: how many times a letter appears
+/// - `r!2`, `r!25`: a letter is not at these positions, counting from 1
+///
+/// For example `c?a?e +r -st r!2`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Query {
+    /// The letters allowed at each position, as 26-bit sets.
+    allowed: [u32; 5],
+    min: [u8; 26],
+    max: [u8; 26],
+}
+
+impl Default for Query {
+    fn default() -> Query {
+        Query {
+            allowed: [ANY; 5],
+            min: [0; 26],
+            max: [5; 26],
+        }
+    }
+}
+
+fn letter(c: u8) -> Result<usize, String> {
+    if c.is_ascii_lowercase() {
+        Ok((c - b'a') as usize)
+    } else {
+        Err(format!("expected a lowercase letter, got {:?}", c as char))
+    }
+}
+
+impl FromStr for Query {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut query = Query::default();
+        for term in s
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|t| !t.is_empty())
+        {
+            let bytes = term.as_bytes();
+            match bytes {
+                [b'+', letters @ ..] | [b'-', letters @ ..] if !letters.is_empty() => {
+                    let mut counts = [0u8; 26];
+                    for &c in letters {
+                        counts[letter(c)?] += 1;
+                    }
+                    for (c, &n) in counts.iter().enumerate().filter(|(_, &n)| n > 0) {
+                        if bytes[0] == b'+' {
+                            query.min[c] = query.min[c].max(n);
+                        } else {
+                            query.max[c] = 0;
+                        }
+                    }
+                }
+                [c, b'!', positions @ ..] if !positions.is_empty() => {
+                    let c = letter(*c)?;
+                    for &p in positions {
+                        let i = match p {
+                            b'1'..=b'5' => (p - b'1') as usize,
+                            _ => return Err(format!("bad position in {term:?}")),
+                        };
+                        query.allowed[i] &= !(1 << c);
+                    }
+                }
+                [c, op @ .., n] if matches!(op, b">=" | b"<=" | b"=") => {
+                    let c = letter(*c)?;
+                    let n = match n {
+                        b'0'..=b'5' => n - b'0',
+                        _ => return Err(format!("bad count in {term:?}")),
+                    };
+                    if op != b"<=" {
+                        query.min[c] = query.min[c].max(n);
+                    }
+                    if op != b">=" {
+                        query.max[c] = query.max[c].min(n);
+                    }
+                }
+                _ if bytes.len() == 5 => {
+                    for (i, &c) in bytes.iter().enumerate() {
+                        if !matches!(c, b'?' | b'.') {
+                            query.allowed[i] &= 1 << letter(c)?;
+                        }
+                    }
+                }
+                _ => return Err(format!("unrecognised term {term:?}")),
+            }
+        }
+        Ok(query)
+    }
+}
+
+impl Query {
+    /// The letters whose counts are restricted, with their bounds.
+    fn counted(&self) -> impl Iterator<Item = (u32, u8, u8)> + '_ {
+        (0..26)
+            .filter(|&c| self.min[c] > 0 || self.max[c] < 5)
+            .map(|c| (c as u32, self.min[c], self.max[c]))
+    }
+
+    pub fn matches(&self, word: Word) -> bool {
+        let mut counts = [0u8; 26];
+        for (i, allowed) in self.allowed.iter().enumerate() {
+            let c = (word >> (5 * i)) & 0x1f;
+            if allowed & (1 << c) == 0 {
+                return false;
+            }
+            counts[c as usize] += 1;
+        }
+        self.counted()
+            .all(|(c, min, max)| (min..=max).contains(&counts[c as usize]))
+    }
+
+    pub fn matchesl<const L: usize>(&self, words: Simd<Word, L>) -> Mask<i32, L>
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        let mut ok = Mask::splat(true);
+        let mut letters = [Simd::splat(0); 5];
+        for (i, (&allowed, c)) in self.allowed.iter().zip(&mut letters).enumerate() {
+            *c = (words >> Simd::splat(5 * i as u32)) & Simd::splat(0x1f);
+            ok &= ((Simd::splat(allowed) >> *c) & Simd::splat(1)).simd_ne(Simd::splat(0));
+        }
+        for (c, min, max) in self.counted() {
+            let mut count = Simd::<u32, L>::splat(0);
+            for &l in &letters {
+                count += l
+                    .simd_eq(Simd::splat(c))
+                    .select(Simd::splat(1), Simd::splat(0));
+            }
+            ok &= count.simd_ge(Simd::splat(min.into())) & count.simd_le(Simd::splat(max.into()));
+        }
+        ok
+    }
+
+    /// The words in `words` matching the query, in order.
+    pub fn filter<const L: usize>(&self, words: &[Word]) -> Vec<Word>
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        let mut kept = Vec::new();
+        let (prefix, simds, suffix) = words.as_simd::<L>();
+        kept.extend(prefix.iter().filter(|&&w| self.matches(w)));
+        for &chunk in simds {
+            let ok = self.matchesl(chunk);
+            for (m, w) in ok.to_array().into_iter().zip(chunk.to_array()) {
+                if m {
+                    kept.push(w);
+                }
+            }
+        }
+        kept.extend(suffix.iter().filter(|&&w| self.matches(w)));
+        kept
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::word_from_str;
+
+    use super::*;
+
+    #[test]
+    fn example_query() {
+        let words = [
+            "crane", "crate", "chase", "craze", "grace", "caste", "cease", "chare", "geese",
+        ]
+        .map(|s| word_from_str(s.as_bytes()).unwrap());
+        let query = "c?a?e, +r -st r!2".parse::<Query>().unwrap();
+        assert_eq!(query.filter::<4>(&words), [words[7]]);
+        let query = "e>=2 +g".parse::<Query>().unwrap();
+        assert_eq!(query.filter::<4>(&words), [words[8]]);
+        let query = "..... a=1 e<=1 c!1".parse::<Query>().unwrap();
+        assert_eq!(query.filter::<4>(&words), [words[4]]);
+
+        for q in ["c?a?e", "+r -st", "e=2", "r!2", "r>=1 a<=0"] {
+            let query = q.parse::<Query>().unwrap();
+            let simd = query.matchesl(Simd::<Word, 8>::from_slice(&words));
+            for (w, m) in words.iter().zip(simd.to_array()) {
+                assert_eq!(query.matches(*w), m);
+            }
+        }
+        assert!("c?a?".parse::<Query>().is_err());
+        assert!("r!6".parse::<Query>().is_err());
+    }
+}
@@ -0,0 +1,356 @@
+use std::{
+    error::Error,
+    fmt, fs,
+    path::Path,
+    simd::{cmp::SimdPartialEq, LaneCount, Simd, SupportedLaneCount},
+    str::FromStr,
+};
+
+use crate::{
+    hard::Hints,
+    metric::Metric,
+    policy::{Anywhere, DupPolicy, Nyt, Policy},
+    squeeze::{grade_n, gradel_n},
+    Grade, Word, BLACK, GREEN, YELLOW,
+};
+
+/// The longest words that fit in a [`Word`].
+pub const MAX_WORD_LENGTH: usize = 6;
+
+/// The rules of a game, loaded from a file of `key = value` lines with `#` comments:
+///
+/// ```text
+/// max_guesses = 8       # guesses allowed
+/// word_length = 5       # letters per word, up to 6
+/// hard = false          # revealed hints must be used
+/// duplicates = nyt      # nyt or anywhere, for five letters only
+/// check_dictionary = no # whether guesses must be in the word list
+/// ```
+///
+/// Missing keys keep the defaults, which are those of the New York Times game.
+/// [`Game`](crate::game::Game), the [`tournament`](crate::tournament) simulator and the
+/// solvers, from [`opener`](crate::opener) to [`minimax`](crate::minimax), all honour them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rules {
+    pub max_guesses: usize,
+    pub word_length: usize,
+    pub hard: bool,
+    pub duplicates: DupPolicy,
+    pub check_dictionary: bool,
+}
+
+impl Default for Rules {
+    fn default() -> Rules {
+        Rules {
+            max_guesses: 6,
+            word_length: 5,
+            hard: false,
+            duplicates: DupPolicy::Nyt,
+            check_dictionary: true,
+        }
+    }
+}
+
+fn parse_bool(key: &str, value: &str) -> Result<bool, String> {
+    match value {
+        "true" | "yes" | "on" => Ok(true),
+        "false" | "no" | "off" => Ok(false),
+        _ => Err(format!("{key} must be true or false, got {value:?}")),
+    }
+}
+
+impl FromStr for Rules {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rules = Rules::default();
+        for (n, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or(format!("line {}: expected key = value", n + 1))?;
+            let (key, value) = (key.trim(), value.trim());
+            let number = |value: &str| {
+                value
+                    .parse()
+                    .map_err(|_| format!("line {}: {key} must be a number", n + 1))
+            };
+            match key {
+                "max_guesses" => rules.max_guesses = number(value)?,
+                "word_length" => rules.word_length = number(value)?,
+                "hard" => rules.hard = parse_bool(key, value)?,
+                "duplicates" => rules.duplicates = value.parse()?,
+                "check_dictionary" => rules.check_dictionary = parse_bool(key, value)?,
+                _ => return Err(format!("line {}: unknown rule {key:?}", n + 1)),
+            }
+        }
+        rules.check()?;
+        Ok(rules)
+    }
+}
+
+impl fmt::Display for Rules {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "max_guesses = {}", self.max_guesses)?;
+        writeln!(f, "word_length = {}", self.word_length)?;
+        writeln!(f, "hard = {}", self.hard)?;
+        let duplicates = match self.duplicates {
+            DupPolicy::Nyt => "nyt",
+            DupPolicy::Anywhere => "anywhere",
+        };
+        writeln!(f, "duplicates = {duplicates}")?;
+        writeln!(f, "check_dictionary = {}", self.check_dictionary)
+    }
+}
+
+impl Rules {
+    pub fn load(path: impl AsRef<Path>) -> Result<Rules, Box<dyn Error>> {
+        Ok(fs::read_to_string(path)?.parse()?)
+    }
+
+    /// Whether the rules can be played. Rules read from a file are always checked; the game and
+    /// the simulator check rules built by hand, and grading panics on an unsupported length.
+    pub fn check(&self) -> Result<(), String> {
+        if self.max_guesses == 0 {
+            return Err("max_guesses must be at least 1".to_owned());
+        }
+        if !(1..=MAX_WORD_LENGTH).contains(&self.word_length) {
+            return Err(format!("word_length must be from 1 to {MAX_WORD_LENGTH}"));
+        }
+        if self.duplicates == DupPolicy::Anywhere && self.word_length != 5 {
+            return Err("duplicates = anywhere needs five-letter words".to_owned());
+        }
+        Ok(())
+    }
+
+    /// The grade of a correct guess.
+    pub fn all_green(&self) -> Grade {
+        GREEN * (((1 << (2 * self.word_length)) - 1) / 3)
+    }
+
+    /// One more than the largest grade.
+    pub fn n_grades(&self) -> usize {
+        self.all_green() as usize + 1
+    }
+
+    pub fn grade(&self, guess: Word, soln: Word) -> Grade {
+        match self.word_length {
+            5 => self.duplicates.grade(guess, soln),
+            1 => grade_n::<1>(guess, soln),
+            2 => grade_n::<2>(guess, soln),
+            3 => grade_n::<3>(guess, soln),
+            4 => grade_n::<4>(guess, soln),
+            6 => grade_n::<6>(guess, soln),
+            n => panic!("unsupported word length {n}; see Rules::check"),
+        }
+    }
+
+    pub fn gradel<const L: usize>(&self, words: Simd<Word, L>, solns: Simd<Word, L>) -> Simd<u32, L>
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        match (self.word_length, self.duplicates) {
+            (5, DupPolicy::Nyt) => Nyt::gradel(words, solns),
+            (5, DupPolicy::Anywhere) => Anywhere::gradel(words, solns),
+            (1, _) => gradel_n::<L, 1>(words, solns),
+            (2, _) => gradel_n::<L, 2>(words, solns),
+            (3, _) => gradel_n::<L, 3>(words, solns),
+            (4, _) => gradel_n::<L, 4>(words, solns),
+            (6, _) => gradel_n::<L, 6>(words, solns),
+            (n, _) => panic!("unsupported word length {n}; see Rules::check"),
+        }
+    }
+
+    /// How many of `solns` give `guess` each grade, indexed by grade.
+    pub fn grade_counts<const L: usize>(&self, guess: Word, solns: &[Word]) -> Vec<u16>
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        let mut counts = vec![0u16; self.n_grades()];
+        let (prefix, simds, suffix) = solns.as_simd::<L>();
+        for &s in prefix.iter().chain(suffix) {
+            counts[self.grade(guess, s) as usize] += 1;
+        }
+        for &s in simds {
+            for graded in self.gradel(Simd::splat(guess), s).to_array() {
+                counts[graded as usize] += 1;
+            }
+        }
+        counts
+    }
+
+    /// Every solution in `solns`, bucketed by the grade `guess` gets against it.
+    pub fn bucket<const L: usize>(&self, guess: Word, solns: &[Word]) -> Vec<Vec<Word>>
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        let mut buckets = vec![Vec::new(); self.n_grades()];
+        let (prefix, simds, suffix) = solns.as_simd::<L>();
+        for &s in prefix.iter().chain(suffix) {
+            buckets[self.grade(guess, s) as usize].push(s);
+        }
+        for &s in simds {
+            let grades = self.gradel(Simd::splat(guess), s);
+            for (graded, s) in grades.to_array().into_iter().zip(s.to_array()) {
+                buckets[graded as usize].push(s);
+            }
+        }
+        buckets
+    }
+
+    /// Expected bits left to identify the answer among `solns` after guessing `guess`.
+    pub fn entropy_after<const L: usize>(&self, guess: Word, solns: &[Word]) -> f32
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        Metric::Entropy.score_with::<L>(self, guess, solns)
+    }
+
+    /// The solutions in `solns` which would give `guess` the grade `graded`.
+    pub fn filter<const L: usize>(&self, guess: Word, graded: Grade, solns: &[Word]) -> Vec<Word>
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        let mut kept = Vec::new();
+        let (prefix, simds, suffix) = solns.as_simd::<L>();
+        kept.extend(prefix.iter().filter(|&&s| self.grade(guess, s) == graded));
+        for &s in simds {
+            let matches = self
+                .gradel(Simd::splat(guess), s)
+                .simd_eq(Simd::splat(graded.into()));
+            for (m, s) in matches.to_array().into_iter().zip(s.to_array()) {
+                if m {
+                    kept.push(s);
+                }
+            }
+        }
+        kept.extend(suffix.iter().filter(|&&s| self.grade(guess, s) == graded));
+        kept
+    }
+
+    /// Whether `guess` may follow `history` in hard mode; always true otherwise. Every green letter
+    /// must stay in place, and every revealed letter must be reused as often as it was revealed.
+    pub fn is_legal(&self, guess: Word, history: &[(Word, Grade)]) -> bool {
+        !self.hard || self.ignored_hint(guess, history).is_none()
+    }
+
+    /// The first guess in `history` whose hints `guess` ignores, in hard mode.
+    pub fn ignored_hint(&self, guess: Word, history: &[(Word, Grade)]) -> Option<Word> {
+        history
+            .iter()
+            .find(|&&(prev, prev_grade)| {
+                !Hints::new(self.word_length, self.duplicates)
+                    .after(prev, prev_grade)
+                    .allows(guess)
+            })
+            .map(|&(prev, _)| prev)
+    }
+
+    pub fn word_from_str(&self, s: &[u8]) -> Option<Word> {
+        if s.len() != self.word_length {
+            return None;
+        }
+        let mut w = 0;
+        for (i, &c) in s.iter().enumerate() {
+            if !c.is_ascii_lowercase() {
+                return None;
+            }
+            w |= u32::from(c - b'a') << (5 * i);
+        }
+        Some(w)
+    }
+
+    pub fn str_from_word(&self, word: Word) -> String {
+        (0..self.word_length)
+            .map(|i| (((word >> (5 * i)) & 0x1f) as u8 + b'a') as char)
+            .collect()
+    }
+
+    /// Parse a grade written with `g` (green), `y` (yellow) and `b` (black), one per letter.
+    pub fn grade_from_str(&self, s: &[u8]) -> Option<Grade> {
+        if s.len() != self.word_length {
+            return None;
+        }
+        let mut g = 0;
+        for (i, &c) in s.iter().enumerate() {
+            g |= match c {
+                b'g' => GREEN,
+                b'y' => YELLOW,
+                b'b' => BLACK,
+                _ => return None,
+            } << (2 * i);
+        }
+        Some(g)
+    }
+
+    pub fn str_from_grade(&self, grade: Grade) -> String {
+        (0..self.word_length)
+            .map(|i| match (grade >> (2 * i)) & 0b11 {
+                GREEN => 'g',
+                YELLOW => 'y',
+                _ => 'b',
+            })
+            .collect()
+    }
+
+    /// Read a file containing one word of the right length per line.
+    pub fn read_words(&self, path: impl AsRef<Path>) -> Result<Vec<Word>, Box<dyn Error>> {
+        fs::read_to_string(path)?
+            .lines()
+            .map(|w| {
+                self.word_from_str(w.as_bytes())
+                    .ok_or(format!("invalid word {w:?}").into())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{squeeze, word_from_str, ALL_GREEN};
+
+    use super::*;
+
+    #[test]
+    fn parses_and_plays() {
+        let rules = "# long game\nmax_guesses = 8\ncheck_dictionary = no # anything goes\n"
+            .parse::<Rules>()
+            .unwrap();
+        assert_eq!(rules.max_guesses, 8);
+        assert!(!rules.check_dictionary);
+        assert_eq!(rules.to_string().parse(), Ok(rules));
+        assert!("word_length = 7".parse::<Rules>().is_err());
+        assert!("word_length = 4\nduplicates = anywhere"
+            .parse::<Rules>()
+            .is_err());
+        assert!("guesses = 8".parse::<Rules>().is_err());
+
+        let five = Rules::default();
+        assert_eq!(five.all_green(), ALL_GREEN);
+        let w = |s: &str| word_from_str(s.as_bytes()).unwrap();
+        let words = [
+            "speed", "abide", "eerie", "geese", "roses", "horse", "aahed", "cigar", "llama",
+        ]
+        .map(w);
+        assert_eq!(
+            five.filter::<4>(w("geese"), five.grade(w("geese"), w("eerie")), &words),
+            squeeze::filter::<4>(w("geese"), squeeze::grade(w("geese"), w("eerie")), &words)
+        );
+
+        let four = "word_length = 4\nhard = yes".parse::<Rules>().unwrap();
+        let (lion, loin, moon) = (
+            four.word_from_str(b"lion").unwrap(),
+            four.word_from_str(b"loin").unwrap(),
+            four.word_from_str(b"moon").unwrap(),
+        );
+        let graded = four.grade(lion, loin);
+        assert_eq!(four.str_from_grade(graded), "gyyg");
+        assert_eq!(four.grade(loin, loin), four.all_green());
+        assert_eq!(four.filter::<4>(lion, graded, &[lion, loin, moon]), [loin]);
+        assert!(four.is_legal(loin, &[(lion, graded)]));
+        assert_eq!(four.ignored_hint(moon, &[(lion, graded)]), Some(lion));
+    }
+}
@@ -0,0 +1,134 @@
+use std::simd::{LaneCount, Simd, SupportedLaneCount};
+
+use crate::Word;
+
+/// Feedback on a guess in some Wordle-like game, numbered from zero.
+pub trait Scorer {
+    /// Number of distinct feedbacks [`Scorer::score`] can return.
+    fn n_feedbacks(&self) -> usize;
+
+    /// The feedback for `guess` when the answer is `soln`.
+    fn score(&self, guess: Word, soln: Word) -> usize;
+
+    /// [`Scorer::score`] for each lane.
+    fn scorel<const L: usize>(&self, guesses: Simd<Word, L>, solns: Simd<Word, L>) -> [usize; L]
+    where
+        LaneCount<L>: SupportedLaneCount;
+}
+
+/// How many solutions in `solns` give `guess` each feedback.
+pub fn counts<const L: usize>(scorer: &impl Scorer, guess: Word, solns: &[Word]) -> Vec<u32>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let mut counts = vec![0; scorer.n_feedbacks()];
+    let (prefix, simds, suffix) = solns.as_simd::<L>();
+    for &soln in prefix.iter().chain(suffix) {
+        counts[scorer.score(guess, soln)] += 1;
+    }
+    for &solns in simds {
+        for fb in scorer.scorel(Simd::splat(guess), solns) {
+            counts[fb] += 1;
+        }
+    }
+    counts
+}
+
+pub fn entropy_after<const L: usize>(scorer: &impl Scorer, guess: Word, solns: &[Word]) -> f32
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    counts::<L>(scorer, guess, solns)
+        .into_iter()
+        .filter(|&n| n > 1)
+        .map(|n| (n as f32).log2() * n as f32)
+        .sum::<f32>()
+        / solns.len() as f32
+}
+
+/// The solutions in `solns` which would give `guess` the feedback `feedback`.
+pub fn filter<const L: usize>(
+    scorer: &impl Scorer,
+    guess: Word,
+    feedback: usize,
+    solns: &[Word],
+) -> Vec<Word>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let mut kept = Vec::new();
+    let (prefix, simds, suffix) = solns.as_simd::<L>();
+    kept.extend(
+        prefix
+            .iter()
+            .filter(|&&s| scorer.score(guess, s) == feedback),
+    );
+    for &answer in simds {
+        let scored = scorer.scorel(Simd::splat(guess), answer);
+        for (fb, answer) in scored.into_iter().zip(answer.to_array()) {
+            if fb == feedback {
+                kept.push(answer);
+            }
+        }
+    }
+    kept.extend(
+        suffix
+            .iter()
+            .filter(|&&s| scorer.score(guess, s) == feedback),
+    );
+    kept
+}
+
+/// The guess from `words` leaving the least entropy among `solns`, preferring ones which could
+/// be the answer.
+pub fn best_guess<const L: usize>(
+    scorer: &impl Scorer,
+    words: &[Word],
+    solns: &[Word],
+) -> Option<Word>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    solns
+        .iter()
+        .chain(words)
+        .map(|&w| (w, entropy_after::<L>(scorer, w, solns)))
+        .reduce(|best, next| if next.1 < best.1 { next } else { best })
+        .map(|(w, _)| w)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        jotto::Jotto,
+        mastermind::{Feedback, Mastermind},
+        peaks::Peaks,
+        word_from_str,
+    };
+
+    use super::*;
+
+    /// Check `scorer`'s SIMD scores against its scalar ones on some awkward words.
+    fn check_simd(scorer: &impl Scorer) {
+        let words = [
+            "roses", "horse", "eerie", "fuzzy", "aahed", "geese", "cigar", "qajaq",
+        ]
+        .map(|s| word_from_str(s.as_bytes()).unwrap());
+        for &guess in &words {
+            let scored = scorer.scorel(Simd::splat(guess), Simd::from_array(words));
+            for (soln, fb) in words.into_iter().zip(scored) {
+                assert_eq!(scorer.score(guess, soln), fb);
+                assert!(fb < scorer.n_feedbacks());
+            }
+        }
+    }
+
+    #[test]
+    fn simd_matches_scalar() {
+        check_simd(&Jotto);
+        check_simd(&Peaks);
+        for feedback in [Feedback::Colors, Feedback::Pegs] {
+            check_simd(&Mastermind::new(5, 26, feedback).unwrap());
+        }
+    }
+}
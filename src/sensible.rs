@@ -1,3 +1,5 @@
+use crate::policy::DupPolicy;
+
 type Word = [u8; 5];
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Hash)]
@@ -30,3 +32,20 @@ pub fn grade(w: Word, soln: Word) -> Grade {
 
     grade
 }
+
+pub fn grade_with(w: Word, soln: Word, policy: DupPolicy) -> Grade {
+    match policy {
+        DupPolicy::Nyt => grade(w, soln),
+        DupPolicy::Anywhere => {
+            let mut grade = [Color::Black; 5];
+            for ((wc, sc), g) in w.into_iter().zip(soln).zip(&mut grade) {
+                if wc == sc {
+                    *g = Color::Green;
+                } else if soln.contains(&wc) {
+                    *g = Color::Yellow;
+                }
+            }
+            grade
+        }
+    }
+}
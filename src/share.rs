@@ -0,0 +1,241 @@
+use std::{
+    collections::HashSet,
+    simd::{LaneCount, SupportedLaneCount},
+    str::FromStr,
+};
+
+use crate::{
+    hard::Hints, preimage::grades, Grade, Word, ALL_GREEN, BLACK, GREEN, N_GRADES, YELLOW,
+};
+
+/// A result as posted with the game's share button: the grades without the guesses.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Share {
+    pub number: usize,
+    /// The number of guesses taken, or `None` for a loss (`X/6`).
+    pub solved_in: Option<usize>,
+    pub max_guesses: usize,
+    pub hard: bool,
+    pub rows: Vec<Grade>,
+}
+
+fn color(c: char) -> Option<Grade> {
+    match c {
+        // the high-contrast palette uses orange and blue
+        '🟩' | '🟧' => Some(GREEN),
+        '🟨' | '🟦' => Some(YELLOW),
+        '⬛' | '⬜' => Some(BLACK),
+        _ => None,
+    }
+}
+
+fn parse_row(line: &str) -> Option<Grade> {
+    let mut grade = 0;
+    let mut n = 0;
+    // emoji may carry a variation selector
+    for c in line.chars().filter(|&c| c != '\u{fe0f}') {
+        if n == 5 {
+            return None;
+        }
+        grade |= color(c)? << (2 * n);
+        n += 1;
+    }
+    (n == 5).then_some(grade)
+}
+
+impl FromStr for Share {
+    type Err = String;
+
+    /// Parse text like `Wordle 1,000 4/6*` followed by one line of emoji per guess. Blank lines
+    /// and anything after the grid are ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().map(str::trim).skip_while(|l| l.is_empty());
+        let header = lines.next().ok_or("empty share")?;
+        let rest = header
+            .strip_prefix("Wordle ")
+            .ok_or_else(|| format!("expected a `Wordle` header, got {header:?}"))?;
+        let (number, score) = rest
+            .rsplit_once(' ')
+            .ok_or_else(|| format!("expected a puzzle number and score in {header:?}"))?;
+        // large numbers are written with separators, e.g. `1,000` or `1 000`
+        let number = number
+            .chars()
+            .filter(|c| !matches!(c, ',' | '.' | ' ' | '\u{a0}'))
+            .collect::<String>()
+            .parse()
+            .map_err(|_| format!("bad puzzle number in {header:?}"))?;
+        let (score, hard) = match score.strip_suffix('*') {
+            Some(score) => (score, true),
+            None => (score, false),
+        };
+        let (taken, max_guesses) = score
+            .split_once('/')
+            .ok_or_else(|| format!("bad score in {header:?}"))?;
+        let max_guesses = max_guesses
+            .parse()
+            .map_err(|_| format!("bad score in {header:?}"))?;
+        let solved_in = match taken {
+            "X" => None,
+            n => Some(n.parse().map_err(|_| format!("bad score in {header:?}"))?),
+        };
+
+        let rows = lines
+            .skip_while(|l| l.is_empty())
+            .map_while(parse_row)
+            .collect::<Vec<_>>();
+        let expected = solved_in.unwrap_or(max_guesses);
+        if rows.len() != expected {
+            return Err(format!("expected {expected} rows, found {}", rows.len()));
+        }
+        let n_won = rows.iter().filter(|&&g| g == ALL_GREEN).count();
+        if solved_in.is_some() != (n_won == 1) || (n_won == 1 && rows.last() != Some(&ALL_GREEN)) {
+            return Err("only the last row of a win may be all green".to_owned());
+        }
+        Ok(Share {
+            number,
+            solved_in,
+            max_guesses,
+            hard,
+            rows,
+        })
+    }
+}
+
+/// Parse every share in `s`, each starting at its `Wordle` header.
+pub fn parse_all(s: &str) -> Result<Vec<Share>, String> {
+    let mut shares = Vec::new();
+    let mut current = String::new();
+    for line in s.lines() {
+        if line.trim_start().starts_with("Wordle ") && !current.trim().is_empty() {
+            shares.push(current.parse()?);
+            current.clear();
+        }
+        current.push_str(line);
+        current.push('\n');
+    }
+    if !current.trim().is_empty() {
+        shares.push(current.parse()?);
+    }
+    Ok(shares)
+}
+
+/// Whether some sequence of guesses from `words` gives every row of `share` if `soln` is the
+/// answer, following the hard-mode rules if the share was played in hard mode.
+pub fn could_produce<const L: usize>(share: &Share, words: &[Word], soln: Word) -> bool
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    could_produce_graded(share, words, &grades::<L>(words, soln), soln)
+}
+
+/// [`could_produce`] given the grade of each of `words` against `soln`.
+fn could_produce_graded(share: &Share, words: &[Word], grades: &[Grade], soln: Word) -> bool {
+    if !share.hard {
+        let mut seen = vec![false; N_GRADES];
+        for &g in grades {
+            seen[g as usize] = true;
+        }
+        // the winning guess is the answer itself, even if it isn't in `words`
+        return share
+            .rows
+            .iter()
+            .all(|&row| row == ALL_GREEN || seen[row as usize]);
+    }
+
+    let options = share
+        .rows
+        .iter()
+        .map(|&row| {
+            if row == ALL_GREEN {
+                return vec![soln];
+            }
+            words
+                .iter()
+                .zip(grades)
+                .filter(|&(_, &g)| g == row)
+                .map(|(&w, _)| w)
+                .collect()
+        })
+        .collect::<Vec<Vec<Word>>>();
+    let mut visited = HashSet::new();
+    hard_path(&share.rows, &options, Hints::default(), &mut visited)
+}
+
+/// Depth-first search for a hard-mode legal guess for each of `rows`. Guesses revealing the
+/// same hints lead to the same place, so each is only tried once per row.
+fn hard_path(
+    rows: &[Grade],
+    options: &[Vec<Word>],
+    hints: Hints,
+    visited: &mut HashSet<(usize, Hints)>,
+) -> bool {
+    let Some((&row, rest)) = rows.split_first() else {
+        return true;
+    };
+    if !visited.insert((rest.len(), hints)) {
+        return false;
+    }
+    options[0].iter().any(|&guess| {
+        hints.allows(guess) && hard_path(rest, &options[1..], hints.after(guess, row), visited)
+    })
+}
+
+/// The answers in `answers` which could have produced every share.
+pub fn infer<const L: usize>(shares: &[Share], words: &[Word], answers: &[Word]) -> Vec<Word>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    answers
+        .iter()
+        .copied()
+        .filter(|&soln| {
+            let grades = grades::<L>(words, soln);
+            shares
+                .iter()
+                .all(|s| could_produce_graded(s, words, &grades, soln))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{squeeze, word_from_str};
+
+    use super::*;
+
+    #[test]
+    fn parses_share_text() {
+        let text = "Wordle 1,000 3/6*\n\n⬛🟨⬛⬛⬛\n🟩🟩⬛🟨⬛\n🟩🟩🟩🟩🟩\n\nanything else";
+        let share = text.parse::<Share>().unwrap();
+        assert_eq!(share.number, 1000);
+        assert_eq!(share.solved_in, Some(3));
+        assert!(share.hard);
+        assert_eq!(share.rows.len(), 3);
+        assert_eq!(share.rows[2], ALL_GREEN);
+
+        let lost = "Wordle 12 X/6\n".to_owned() + &"🟧🟦⬜⬜⬜\n".repeat(6);
+        assert_eq!(lost.parse::<Share>().unwrap().solved_in, None);
+        assert!("Wordle 12 2/6\n🟩🟩🟩🟩🟩\n".parse::<Share>().is_err());
+        assert_eq!(parse_all(&format!("{text}\n{lost}")).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn hard_mode_needs_a_legal_sequence() {
+        let w = |s: &str| word_from_str(s.as_bytes()).unwrap();
+        let words = ["crate", "trace", "caret", "lions", "cruel"].map(w);
+        let g = |guess: &str| squeeze::grade(w(guess), w("crate"));
+        let mut share = Share {
+            number: 0,
+            solved_in: Some(3),
+            max_guesses: 6,
+            hard: false,
+            rows: vec![g("cruel"), g("lions"), ALL_GREEN],
+        };
+        assert!(could_produce::<8>(&share, &words, w("crate")));
+        // `lions` drops the revealed c, r and e
+        share.hard = true;
+        assert!(!could_produce::<8>(&share, &words, w("crate")));
+        share.rows = vec![g("lions"), g("cruel"), ALL_GREEN];
+        assert!(could_produce::<8>(&share, &words, w("crate")));
+    }
+}
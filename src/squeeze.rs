@@ -4,7 +4,11 @@ use std::simd::{
     LaneCount, Simd, SupportedLaneCount,
 };
 
-use crate::{Grade, Word, BLACK, GREEN, N_GRADES, YELLOW};
+use crate::{
+    packed,
+    policy::{Nyt, Policy},
+    Grade, Word, BLACK, GREEN, N_GRADES, YELLOW,
+};
 
 pub fn grade(guess: Word, soln: Word) -> Grade {
     let mut yellow_bank = 0u64;
@@ -93,25 +97,188 @@ where
     grade
 }
 
-pub fn entropy_after<const L: usize>(word: Word, solns: &[Word]) -> f32
+/// Grade codes of `N` symbols drawn from an alphabet of up to 32, packed like [`Word`]s.
+/// Any symbol may repeat up to `N` times.
+pub fn grade_n<const N: usize>(guess: Word, soln: Word) -> Grade {
+    packed::grade_n::<N>(guess, soln)
+}
+
+/// Lane-wise [`grade_n`].
+pub fn gradel_n<const L: usize, const N: usize>(
+    words: Simd<Word, L>,
+    solns: Simd<Word, L>,
+) -> Simd<u32, L>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    // three bits per symbol so that a symbol can fill a code of 6, eight symbols per bank
+    let mut yellows = [Simd::<u32, L>::splat(0); 4];
+    let mut grade = Simd::splat(0);
+    let mut guess2 = words;
+    let mut soln2 = solns;
+
+    for _ in 0..N {
+        let matches_bottom_5 = ((guess2 ^ soln2) & Simd::splat(0x1f)).simd_eq(Simd::splat(0));
+        grade |= matches_bottom_5.select(
+            Simd::splat((GREEN as u32) << (2 * N)),
+            Simd::splat(BLACK as u32),
+        );
+        let sc = soln2 & Simd::splat(0x1f);
+        let bank = sc >> Simd::splat(3);
+        let one = Simd::splat(1) << (Simd::splat(3) * (sc & Simd::splat(0b111)));
+        for (k, yellow) in yellows.iter_mut().enumerate() {
+            *yellow += (!matches_bottom_5 & bank.simd_eq(Simd::splat(k as u32)))
+                .select(one, Simd::splat(0));
+        }
+        grade >>= 2;
+        guess2 >>= 5;
+        soln2 >>= 5;
+    }
+
+    for i in 0..N {
+        let c = (words >> Simd::splat(5 * i as u32)) & Simd::splat(0x1f);
+        let bank = c >> Simd::splat(3);
+        let shift = Simd::splat(3) * (c & Simd::splat(0b111));
+
+        let needs_yellow =
+            (grade & Simd::splat(0b11 << (2 * i))).simd_eq(Simd::splat(BLACK as u32));
+        let mut n_yellow = Simd::splat(0);
+        for (k, &yellow) in yellows.iter().enumerate() {
+            n_yellow |= bank
+                .simd_eq(Simd::splat(k as u32))
+                .select(yellow, Simd::splat(0));
+        }
+        let got_yellow =
+            needs_yellow & ((n_yellow >> shift) & Simd::splat(0b111)).simd_gt(Simd::splat(0));
+
+        grade |= got_yellow.select(Simd::splat((YELLOW as u32) << (2 * i)), Simd::splat(0));
+
+        let one = Simd::splat(1) << shift;
+        for (k, yellow) in yellows.iter_mut().enumerate() {
+            *yellow -=
+                (got_yellow & bank.simd_eq(Simd::splat(k as u32))).select(one, Simd::splat(0));
+        }
+    }
+
+    grade
+}
+
+/// Grade with every non-green letter marked yellow if it appears anywhere in `soln`, however
+/// many times it has already been marked.
+pub fn grade_anywhere(guess: Word, soln: Word) -> Grade {
+    let mut letters = 0u32;
+    let mut soln2 = soln;
+    for _ in 0..5 {
+        letters |= 1 << (soln2 & 0x1f);
+        soln2 >>= 5;
+    }
+
+    let mut grade = 0u16;
+    for i in 0..5 {
+        let c = (guess >> (5 * i)) & 0x1f;
+        if c == (soln >> (5 * i)) & 0x1f {
+            grade |= GREEN << (2 * i);
+        } else if letters & (1 << c) != 0 {
+            grade |= YELLOW << (2 * i);
+        }
+    }
+    grade
+}
+
+pub fn gradel_anywhere<const L: usize>(words: Simd<Word, L>, solns: Simd<Word, L>) -> Simd<u32, L>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let mut letters = Simd::<u32, L>::splat(0);
+    let mut soln2 = solns;
+    for _ in 0..5 {
+        letters |= Simd::splat(1) << (soln2 & Simd::splat(0x1f));
+        soln2 >>= 5;
+    }
+
+    let mut grade = Simd::splat(0);
+    for i in 0..5 {
+        let shift = Simd::splat(5 * i);
+        let c = (words >> shift) & Simd::splat(0x1f);
+        let is_green = c.simd_eq((solns >> shift) & Simd::splat(0x1f));
+        let is_yellow = !is_green & (letters & (Simd::splat(1) << c)).simd_ne(Simd::splat(0));
+        grade |= is_green.select(Simd::splat((GREEN as u32) << (2 * i)), Simd::splat(0));
+        grade |= is_yellow.select(Simd::splat((YELLOW as u32) << (2 * i)), Simd::splat(0));
+    }
+    grade
+}
+
+pub fn grade_counts<const L: usize>(word: Word, solns: &[Word]) -> [u16; N_GRADES]
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    grade_counts_with::<Nyt, L>(word, solns)
+}
+
+pub fn grade_counts_with<P: Policy, const L: usize>(word: Word, solns: &[Word]) -> [u16; N_GRADES]
 where
     LaneCount<L>: SupportedLaneCount,
 {
     let mut word_count = [0u16; N_GRADES];
     let (prefix, simds, suffix) = solns.as_simd();
     for &answer in prefix {
-        word_count[grade(word, answer) as usize] += 1;
+        word_count[P::grade(word, answer) as usize] += 1;
     }
     for &answer in suffix {
-        word_count[grade(word, answer) as usize] += 1;
+        word_count[P::grade(word, answer) as usize] += 1;
     }
     for &answer in simds {
-        let grades: Simd<usize, L> = gradel(Simd::splat(word), answer).cast();
+        let grades: Simd<usize, L> = P::gradel(Simd::splat(word), answer).cast();
         for graded in grades.to_array() {
             word_count[graded] += 1;
         }
     }
     word_count
+}
+
+/// The solutions in `solns` which would give `guess` the grade `graded`.
+pub fn filter<const L: usize>(guess: Word, graded: Grade, solns: &[Word]) -> Vec<Word>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    filter_with::<Nyt, L>(guess, graded, solns)
+}
+
+pub fn filter_with<P: Policy, const L: usize>(
+    guess: Word,
+    graded: Grade,
+    solns: &[Word],
+) -> Vec<Word>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let mut kept = Vec::new();
+    let (prefix, simds, suffix) = solns.as_simd::<L>();
+    kept.extend(prefix.iter().filter(|&&s| P::grade(guess, s) == graded));
+    for &answer in simds {
+        let matches = P::gradel(Simd::splat(guess), answer).simd_eq(Simd::splat(graded.into()));
+        for (m, answer) in matches.to_array().into_iter().zip(answer.to_array()) {
+            if m {
+                kept.push(answer);
+            }
+        }
+    }
+    kept.extend(suffix.iter().filter(|&&s| P::grade(guess, s) == graded));
+    kept
+}
+
+pub fn entropy_after<const L: usize>(word: Word, solns: &[Word]) -> f32
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    entropy_after_with::<Nyt, L>(word, solns)
+}
+
+pub fn entropy_after_with<P: Policy, const L: usize>(word: Word, solns: &[Word]) -> f32
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    grade_counts_with::<P, L>(word, solns)
         .into_iter()
         .filter(|&n| n > 1)
         .map(|n| (n as f32).log2() * n as f32)
@@ -195,4 +362,65 @@ mod tests {
             assert_eq!(seq_grades[i], simd_grades[i] as u16);
         }
     }
+
+    #[test]
+    fn filter_keeps_order() {
+        let solns = [
+            "cigar", "rebut", "sissy", "humph", "awake", "blush", "focal", "evade", "naval",
+            "serve", "heath", "dwarf", "model",
+        ]
+        .map(|s| word_from_str(s.as_bytes()).unwrap());
+        let guess = word_from_str(b"trace").unwrap();
+        for &soln in &solns {
+            let graded = grade(guess, soln);
+            let expected = solns
+                .iter()
+                .copied()
+                .filter(|&s| grade(guess, s) == graded)
+                .collect::<Vec<_>>();
+            assert_eq!(filter::<4>(guess, graded, &solns), expected);
+        }
+    }
+
+    #[test]
+    fn general_length_matches_words() {
+        let words = [
+            "roses", "horse", "eerie", "geese", "aahed", "cigar", "qajaq", "fuzzy",
+        ]
+        .map(|s| word_from_str(s.as_bytes()).unwrap());
+        for &guess in &words {
+            let simd_grades = gradel_n::<8, 5>(Simd::splat(guess), Simd::from_array(words));
+            for (soln, graded) in words.into_iter().zip(simd_grades.to_array()) {
+                assert_eq!(grade_n::<5>(guess, soln), grade(guess, soln));
+                assert_eq!(graded as u16, grade(guess, soln));
+            }
+        }
+    }
+
+    #[test]
+    fn four_of_a_kind() {
+        // 1111 against 1112 in Mastermind
+        let guess = 0b00001_00001_00001_00001;
+        let soln = 0b00010_00001_00001_00001;
+        let expected = GREEN * 0b010101;
+        assert_eq!(grade_n::<4>(guess, soln), expected);
+        assert_eq!(
+            gradel_n::<1, 4>(Simd::splat(guess), Simd::splat(soln))[0] as u16,
+            expected
+        );
+    }
+
+    #[test]
+    fn anywhere_marks_every_copy() {
+        let guess = word_from_str(b"speed").unwrap();
+        let soln = word_from_str(b"abide").unwrap();
+        let expected = (YELLOW << 4) | (YELLOW << 6) | (YELLOW << 8);
+        assert_eq!(grade_anywhere(guess, soln), expected);
+        assert_eq!(
+            gradel_anywhere::<1>(Simd::splat(guess), Simd::splat(soln))[0] as u16,
+            expected
+        );
+        // the usual rule only has one e to give
+        assert_eq!(grade(guess, soln), (YELLOW << 4) | (YELLOW << 8));
+    }
 }
@@ -0,0 +1,388 @@
+use std::{error::Error, fmt, fs, io, path::Path, str::FromStr};
+
+use crate::{game::Game, rules::Rules, Word};
+
+/// The version written by [`Stats`]'s `Display`; older versions will still be read.
+pub const VERSION: u32 = 1;
+
+const HEADER: &str = "wordle-stats";
+
+/// A finished game.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    pub id: String,
+    pub answer: String,
+    pub hard: bool,
+    pub max_guesses: usize,
+    /// The guesses taken, or `None` if the game was lost.
+    pub guesses: Option<usize>,
+    /// The guesses the bot took on the same answer, if known.
+    pub bot: Option<usize>,
+}
+
+/// A game in progress, enough to carry on where it was left.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Saved {
+    pub id: String,
+    pub rules: Rules,
+    pub answer: Word,
+    pub guesses: Vec<Word>,
+}
+
+impl Saved {
+    pub fn new(id: &str, game: &Game) -> Saved {
+        Saved {
+            id: id.to_owned(),
+            rules: *game.rules(),
+            answer: game.answer(),
+            guesses: game.history().iter().map(|&(g, _)| g).collect(),
+        }
+    }
+
+    pub fn resume(&self) -> Result<Game, String> {
+        Game::resume(self.answer, self.rules, &self.guesses)
+    }
+}
+
+/// A player's finished games and games in progress, kept in a text file which other tools
+/// may read:
+///
+/// ```text
+/// wordle-stats 1
+/// game    #1234   crate   hard    6   4   3
+/// game    seed:7  ready   normal  6   x   -
+/// save    #1235   max_guesses=6;word_length=5;hard=false;duplicates=nyt;check_dictionary=true   salet,corny
+/// ```
+///
+/// The first line names the format and its version. Every other line has fields separated by
+/// single tabs, shown as spaces above. Blank lines and lines starting with `#` are ignored.
+///
+/// - `game`: a finished game, oldest first: an id, the answer, `hard` or `normal`, the guesses
+///   allowed, the guesses taken or `x` for a loss, and the guesses the bot took or `-`.
+/// - `save`: a game in progress, at most one per id: an id, the rules as `key=value` pairs
+///   separated by `;`, and the guesses so far separated by `,`, with the answer first.
+///
+/// Ids are `#n` for a daily puzzle and `seed:n` for a random one, and each is finished at most
+/// once. A game is won in from 1 to the guesses allowed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub records: Vec<Record>,
+    pub saved: Vec<Saved>,
+}
+
+impl Stats {
+    /// Read the stats in `path`, or start afresh if there is no such file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Stats, Box<dyn Error>> {
+        match fs::read_to_string(path) {
+            Ok(s) => Ok(s.parse()?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Stats::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Write the stats to `path`, replacing it only once fully written.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, self.to_string())?;
+        fs::rename(tmp, path)
+    }
+
+    /// The finished game for `id`, if there is one.
+    pub fn record(&self, id: &str) -> Option<&Record> {
+        self.records.iter().find(|r| r.id == id)
+    }
+
+    pub fn saved(&self, id: &str) -> Option<&Saved> {
+        self.saved.iter().find(|s| s.id == id)
+    }
+
+    /// Keep `game` as the game in progress for `id`, replacing any saved before.
+    pub fn save_game(&mut self, id: &str, game: &Game) {
+        self.saved.retain(|s| s.id != id);
+        self.saved.push(Saved::new(id, game));
+    }
+
+    /// Record the finished `game` for `id`, and drop it from the games in progress. Each id is
+    /// recorded only once, so replaying a puzzle can't count twice.
+    pub fn finish(&mut self, id: &str, game: &Game, bot: Option<usize>) -> Result<(), String> {
+        if self.record(id).is_some() {
+            return Err(format!("{id} has already been played"));
+        }
+        self.saved.retain(|s| s.id != id);
+        self.records.push(Record {
+            id: id.to_owned(),
+            answer: game.rules().str_from_word(game.answer()),
+            hard: game.is_hard(),
+            max_guesses: game.max_guesses(),
+            guesses: game.is_won().then_some(game.history().len()),
+            bot,
+        });
+        Ok(())
+    }
+
+    pub fn played(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn won(&self) -> usize {
+        self.records.iter().filter(|r| r.guesses.is_some()).count()
+    }
+
+    /// Wins in a row up to the latest game.
+    pub fn current_streak(&self) -> usize {
+        self.records
+            .iter()
+            .rev()
+            .take_while(|r| r.guesses.is_some())
+            .count()
+    }
+
+    pub fn max_streak(&self) -> usize {
+        self.records
+            .iter()
+            .fold((0, 0), |(max, run), r| match r.guesses {
+                Some(_) => (max.max(run + 1), run + 1),
+                None => (max, 0),
+            })
+            .0
+    }
+
+    /// How many games were won in each number of guesses, from 1.
+    pub fn distribution(&self) -> Vec<usize> {
+        let longest = self
+            .records
+            .iter()
+            .map(|r| r.max_guesses.max(r.guesses.unwrap_or(0)))
+            .max()
+            .unwrap_or(6);
+        let mut counts = vec![0; longest];
+        for g in self.records.iter().filter_map(|r| r.guesses) {
+            counts[g - 1] += 1;
+        }
+        counts
+    }
+
+    /// The average guesses over the games won.
+    pub fn average(&self) -> Option<f64> {
+        let won = self.records.iter().filter_map(|r| r.guesses);
+        let (sum, n) = won.fold((0, 0), |(sum, n), g| (sum + g, n + 1));
+        (n > 0).then(|| sum as f64 / n as f64)
+    }
+
+    /// How many more guesses than the bot were taken on average, over the games both won.
+    pub fn versus_bot(&self) -> Option<f64> {
+        let both = self
+            .records
+            .iter()
+            .filter_map(|r| Some(r.guesses? as f64 - r.bot? as f64));
+        let (sum, n) = both.fold((0.0, 0), |(sum, n), d| (sum + d, n + 1));
+        (n > 0).then(|| sum / n as f64)
+    }
+
+    pub fn hard_games(&self) -> usize {
+        self.records.iter().filter(|r| r.hard).count()
+    }
+
+    /// The statistics for people, a few lines long.
+    pub fn summary(&self) -> String {
+        let mut out = format!(
+            "played {}, won {}, current streak {}, max streak {}, hard mode {}\n",
+            self.played(),
+            self.won(),
+            self.current_streak(),
+            self.max_streak(),
+            self.hard_games()
+        );
+        if let Some(average) = self.average() {
+            out += &format!("average {average:.2} guesses");
+            if let Some(diff) = self.versus_bot() {
+                out += &format!(", {diff:+.2} against the bot");
+            }
+            out += "\n";
+        }
+        let distribution = self.distribution();
+        let most = distribution.iter().copied().max().unwrap_or(0).max(1);
+        for (i, &n) in distribution.iter().enumerate() {
+            out += &format!("{}: {} {n}\n", i + 1, "#".repeat((20 * n).div_ceil(most)));
+        }
+        out
+    }
+}
+
+fn optional(s: &str, none: &str) -> Result<Option<usize>, String> {
+    if s == none {
+        return Ok(None);
+    }
+    s.parse()
+        .map(Some)
+        .map_err(|_| format!("expected a number or {none:?}, got {s:?}"))
+}
+
+fn parse_line(line: &str, stats: &mut Stats) -> Result<(), String> {
+    let fields = line.split('\t').collect::<Vec<_>>();
+    match fields[..] {
+        ["game", id, answer, mode, max_guesses, guesses, bot] => {
+            if stats.record(id).is_some() {
+                return Err(format!("{id} is recorded twice"));
+            }
+            let max_guesses = max_guesses
+                .parse()
+                .map_err(|_| format!("bad guess limit {max_guesses:?}"))?;
+            let guesses = optional(guesses, "x")?;
+            if let Some(g) = guesses.filter(|&g| g == 0 || g > max_guesses) {
+                return Err(format!("{id} can't be won in {g} guesses"));
+            }
+            stats.records.push(Record {
+                id: id.to_owned(),
+                answer: answer.to_owned(),
+                hard: match mode {
+                    "hard" => true,
+                    "normal" => false,
+                    _ => return Err(format!("expected hard or normal, got {mode:?}")),
+                },
+                max_guesses,
+                guesses,
+                bot: optional(bot, "-")?,
+            });
+        }
+        ["save", id, rules, guesses] => {
+            if stats.saved(id).is_some() {
+                return Err(format!("{id} is saved twice"));
+            }
+            let rules = rules.replace(';', "\n").parse::<Rules>()?;
+            let mut words = guesses.split(',').map(|w| {
+                rules
+                    .word_from_str(w.as_bytes())
+                    .ok_or(format!("invalid word {w:?}"))
+            });
+            let answer = words.next().unwrap()?;
+            stats.saved.push(Saved {
+                id: id.to_owned(),
+                rules,
+                answer,
+                guesses: words.collect::<Result<_, _>>()?,
+            });
+        }
+        _ => return Err(format!("unrecognised line {line:?}")),
+    }
+    Ok(())
+}
+
+impl FromStr for Stats {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().enumerate();
+        let version = lines
+            .next()
+            .and_then(|(_, header)| header.strip_prefix(HEADER)?.trim().parse::<u32>().ok())
+            .ok_or(format!(
+                "not a stats file; expected {HEADER} {VERSION} first"
+            ))?;
+        if version > VERSION {
+            return Err(format!(
+                "stats file version {version} is newer than {VERSION}"
+            ));
+        }
+        let mut stats = Stats::default();
+        for (n, line) in lines {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            parse_line(line, &mut stats).map_err(|e| format!("line {}: {e}", n + 1))?;
+        }
+        Ok(stats)
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{HEADER} {VERSION}")?;
+        for r in &self.records {
+            writeln!(
+                f,
+                "game\t{}\t{}\t{}\t{}\t{}\t{}",
+                r.id,
+                r.answer,
+                if r.hard { "hard" } else { "normal" },
+                r.max_guesses,
+                r.guesses.map_or("x".to_owned(), |g| g.to_string()),
+                r.bot.map_or("-".to_owned(), |g| g.to_string()),
+            )?;
+        }
+        for s in &self.saved {
+            let rules = s
+                .rules
+                .to_string()
+                .trim_end()
+                .replace('\n', ";")
+                .replace(' ', "");
+            let words = [s.answer]
+                .iter()
+                .chain(&s.guesses)
+                .map(|&w| s.rules.str_from_word(w))
+                .collect::<Vec<_>>();
+            writeln!(f, "save\t{}\t{rules}\t{}", s.id, words.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::word_from_str;
+
+    use super::*;
+
+    #[test]
+    fn round_trips_and_counts() {
+        let w = |s: &[u8]| word_from_str(s).unwrap();
+        let mut stats = Stats::default();
+        for (id, answer, guesses, bot) in [
+            ("#1", b"crate", &[b"trace", b"crate"][..], Some(3)),
+            ("#2", b"lions", &[b"crate"; 6][..], None),
+            ("#3", b"react", &[b"crate", b"trace", b"react"][..], Some(3)),
+        ] {
+            let mut game = Game::new(w(answer), 6, id == "#3");
+            for &g in guesses {
+                game.guess(w(g), &[w(g)]).unwrap();
+            }
+            stats.finish(id, &game, bot).unwrap();
+        }
+        let mut game = Game::new(w(b"lions"), 6, false);
+        game.guess(w(b"crate"), &[w(b"crate")]).unwrap();
+        stats.save_game("seed:4", &game);
+
+        assert_eq!((stats.played(), stats.won()), (3, 2));
+        assert_eq!((stats.current_streak(), stats.max_streak()), (1, 1));
+        assert_eq!(stats.distribution(), [0, 1, 1, 0, 0, 0]);
+        assert_eq!(stats.average(), Some(2.5));
+        assert_eq!(stats.versus_bot(), Some(-0.5));
+        assert_eq!(stats.hard_games(), 1);
+
+        let text = stats.to_string();
+        assert!(text.starts_with("wordle-stats 1\n"));
+        let read = text.parse::<Stats>().unwrap();
+        assert_eq!(read, stats);
+        let resumed = read.saved("seed:4").unwrap().resume().unwrap();
+        assert_eq!(resumed.history(), game.history());
+
+        assert!(stats.finish("#1", &game, None).is_err());
+        assert_eq!(stats.played(), 3);
+
+        assert!("wordle-stats 2\n".parse::<Stats>().is_err());
+        assert!("wordle-stats 1\ngame\t#1\tcrate\tnormal\t6\t0\t-\n"
+            .parse::<Stats>()
+            .is_err());
+        let twice = "game\t#1\tcrate\tnormal\t6\t3\t-\n".repeat(2);
+        assert!(format!("wordle-stats 1\n{twice}").parse::<Stats>().is_err());
+        let save = text.lines().find(|l| l.starts_with("save\t")).unwrap();
+        assert!(format!("wordle-stats 1\n{save}\n{save}\n")
+            .parse::<Stats>()
+            .is_err());
+        assert!("wordle-stats 1\ngame\t#1\tcrate\n"
+            .parse::<Stats>()
+            .is_err());
+    }
+}
@@ -0,0 +1,417 @@
+use std::{
+    fmt,
+    simd::{LaneCount, SupportedLaneCount},
+    thread::scope,
+};
+
+use crate::{metric::Metric, minimax::Tree, rng::Rng, rules::Rules, Grade, Word};
+
+/// A way of playing: picks each guess from what has happened so far.
+pub trait Strategy<const L: usize>: Sync
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    fn name(&self) -> String;
+
+    /// The next guess, given the guesses so far with their grades and the answers still possible.
+    fn guess(&self, history: &[(Word, Grade)], candidates: &[Word]) -> Word;
+}
+
+/// Plays whichever guess scores best under `metric` and `rules`, preferring candidates on ties.
+/// With `candidates_only` only words which could be the answer are guessed.
+pub struct Greedy<'a> {
+    pub words: &'a [Word],
+    pub metric: Metric,
+    pub opener: Option<Word>,
+    pub rules: Rules,
+    pub candidates_only: bool,
+}
+
+impl<const L: usize> Strategy<L> for Greedy<'_>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    fn name(&self) -> String {
+        let metric = match self.metric {
+            Metric::Entropy => "entropy",
+            Metric::ExpectedSize => "expected",
+            Metric::WorstCase => "worst",
+        };
+        let only = if self.candidates_only {
+            "/candidates"
+        } else {
+            ""
+        };
+        let hard = if self.rules.hard { "/hard" } else { "" };
+        format!("{metric}{only}{hard}")
+    }
+
+    fn guess(&self, history: &[(Word, Grade)], candidates: &[Word]) -> Word {
+        if let (Some(opener), []) = (self.opener, history) {
+            return opener;
+        }
+        if candidates.len() <= 2 {
+            return candidates[0];
+        }
+        let words = if self.candidates_only {
+            &[]
+        } else {
+            self.words
+        };
+        candidates
+            .iter()
+            .chain(words)
+            .filter(|&&w| self.rules.is_legal(w, history))
+            .map(|&w| (w, self.metric.score_with::<L>(&self.rules, w, candidates)))
+            .fold(None, |best: Option<(Word, f32)>, next| match best {
+                Some(best) if best.1 <= next.1 => Some(best),
+                _ => Some(next),
+            })
+            .map_or(candidates[0], |(w, _)| w)
+    }
+}
+
+/// Follows a decision tree, such as one found by [`crate::minimax`], guessing the first
+/// candidate once off the tree.
+pub struct Follow {
+    pub name: String,
+    pub tree: Tree,
+}
+
+impl<const L: usize> Strategy<L> for Follow
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn guess(&self, history: &[(Word, Grade)], candidates: &[Word]) -> Word {
+        let mut node = &self.tree;
+        for &(guess, graded) in history {
+            let next = (node.guess == guess)
+                .then(|| node.branches.iter().find(|&&(g, _)| g == graded))
+                .flatten();
+            match next {
+                Some((_, t)) => node = t,
+                None => return candidates[0],
+            }
+        }
+        node.guess
+    }
+}
+
+/// The guesses `strategy` takes to find `answer` among `answers`, or `None` if it hasn't within
+/// the guesses `rules` allow. A guess breaking the rules is a bug in the strategy, so an error.
+pub fn play<const L: usize>(
+    strategy: &dyn Strategy<L>,
+    answer: Word,
+    answers: &[Word],
+    rules: &Rules,
+) -> Result<Option<usize>, String>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    rules.check()?;
+    let mut candidates = answers.to_vec();
+    let mut history = Vec::new();
+    for turn in 1..=rules.max_guesses {
+        let guess = strategy.guess(&history, &candidates);
+        if !rules.is_legal(guess, &history) {
+            return Err(format!(
+                "{} played {} against {}, ignoring a hard-mode hint",
+                strategy.name(),
+                rules.str_from_word(guess),
+                rules.str_from_word(answer)
+            ));
+        }
+        let graded = rules.grade(guess, answer);
+        if graded == rules.all_green() {
+            return Ok(Some(turn));
+        }
+        candidates = rules.filter::<L>(guess, graded, &candidates);
+        history.push((guess, graded));
+    }
+    Ok(None)
+}
+
+/// How many guesses each strategy took for each answer, `None` for a loss.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Results {
+    pub names: Vec<String>,
+    pub answers: Vec<Word>,
+    /// Indexed by strategy, then answer.
+    pub guesses: Vec<Vec<Option<usize>>>,
+    pub max_guesses: usize,
+}
+
+/// Play every strategy against every answer in `answers`, splitting the answers between
+/// `n_threads` threads. Fails if any strategy breaks the rules.
+pub fn run<const L: usize>(
+    strategies: &[&dyn Strategy<L>],
+    answers: &[Word],
+    rules: &Rules,
+    n_threads: usize,
+) -> Result<Results, String>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let chunk_size = answers.len().div_ceil(n_threads.max(1)).max(1);
+    let per_answer = scope(|s| {
+        let handles: Vec<_> = answers
+            .chunks(chunk_size)
+            .map(|chunk| {
+                s.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|&answer| {
+                            strategies
+                                .iter()
+                                .map(|&st| play::<L>(st, answer, answers, rules))
+                                .collect::<Result<Vec<_>, _>>()
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+    Ok(Results {
+        names: strategies.iter().map(|st| st.name()).collect(),
+        answers: answers.to_vec(),
+        guesses: (0..strategies.len())
+            .map(|i| per_answer.iter().map(|row| row[i]).collect())
+            .collect(),
+        max_guesses: rules.max_guesses,
+    })
+}
+
+/// Wins, losses and ties of one strategy against another, answer by answer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HeadToHead {
+    pub wins: usize,
+    pub losses: usize,
+    pub ties: usize,
+}
+
+impl HeadToHead {
+    /// The two-sided sign test: the chance of a split at least this uneven between wins and
+    /// losses if neither strategy were better. Ties are left out.
+    pub fn p_value(&self) -> f64 {
+        let n = self.wins + self.losses;
+        let k = self.wins.min(self.losses);
+        // sum the binomial tail in log space, since 2^n overflows
+        let ln_half = 0.5f64.ln() * n as f64;
+        let mut ln_choose = 0.0;
+        let mut tail = 0.0;
+        for i in 0..=k {
+            if i > 0 {
+                ln_choose += ((n - i + 1) as f64 / i as f64).ln();
+            }
+            tail += (ln_choose + ln_half).exp();
+        }
+        (2.0 * tail).min(1.0)
+    }
+}
+
+/// One line of the leaderboard.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Standing {
+    pub strategy: usize,
+    pub mean: f64,
+    pub losses: usize,
+    pub worst: usize,
+    /// How many answers took each number of guesses, from 1.
+    pub distribution: Vec<usize>,
+}
+
+impl Results {
+    /// The guesses taken for answer `i`, counting a loss as one more than allowed.
+    fn score(&self, strategy: usize, i: usize) -> usize {
+        self.guesses[strategy][i].unwrap_or(self.max_guesses + 1)
+    }
+
+    fn mean_over(&self, strategy: usize, indices: impl Iterator<Item = usize>) -> f64 {
+        let (sum, n) = indices.fold((0, 0), |(sum, n), i| (sum + self.score(strategy, i), n + 1));
+        sum as f64 / n.max(1) as f64
+    }
+
+    /// How strategy `a` did against `b` on each answer, fewer guesses winning.
+    pub fn head_to_head(&self, a: usize, b: usize) -> HeadToHead {
+        let mut h = HeadToHead::default();
+        for i in 0..self.answers.len() {
+            match self.score(a, i).cmp(&self.score(b, i)) {
+                std::cmp::Ordering::Less => h.wins += 1,
+                std::cmp::Ordering::Greater => h.losses += 1,
+                std::cmp::Ordering::Equal => h.ties += 1,
+            }
+        }
+        h
+    }
+
+    /// A 95% bootstrap interval for how many more guesses `a` takes on average than `b`,
+    /// resampling the answers `rounds` times.
+    pub fn bootstrap(&self, a: usize, b: usize, rounds: usize, rng: &mut Rng) -> (f64, f64) {
+        let n = self.answers.len();
+        let diffs = (0..n)
+            .map(|i| self.score(a, i) as f64 - self.score(b, i) as f64)
+            .collect::<Vec<_>>();
+        let mut means = (0..rounds.max(1))
+            .map(|_| (0..n).map(|_| diffs[rng.below(n)]).sum::<f64>() / n as f64)
+            .collect::<Vec<_>>();
+        means.sort_by(f64::total_cmp);
+        let at = |q: f64| means[((means.len() - 1) as f64 * q).round() as usize];
+        (at(0.025), at(0.975))
+    }
+
+    /// How often each strategy has the best mean on random subsets of `size` answers, as a
+    /// fraction of `rounds`, with ties shared.
+    pub fn subset_wins(&self, size: usize, rounds: usize, rng: &mut Rng) -> Vec<f64> {
+        let n = self.answers.len();
+        let size = size.min(n);
+        let mut wins = vec![0.0; self.names.len()];
+        let mut order = (0..n).collect::<Vec<_>>();
+        for _ in 0..rounds {
+            // the first `size` of a partial shuffle
+            for i in 0..size {
+                let j = i + rng.below(n - i);
+                order.swap(i, j);
+            }
+            let means = (0..self.names.len())
+                .map(|s| self.mean_over(s, order[..size].iter().copied()))
+                .collect::<Vec<_>>();
+            let best = means.iter().copied().fold(f64::INFINITY, f64::min);
+            let n_best = means.iter().filter(|&&m| m == best).count();
+            for (w, &m) in wins.iter_mut().zip(&means) {
+                if m == best {
+                    *w += 1.0 / n_best as f64;
+                }
+            }
+        }
+        wins.iter().map(|w| w / rounds.max(1) as f64).collect()
+    }
+
+    /// Every strategy, fewest losses then fewest guesses on average first.
+    pub fn leaderboard(&self) -> Vec<Standing> {
+        let mut standings = (0..self.names.len())
+            .map(|s| {
+                let mut distribution = vec![0; self.max_guesses];
+                for g in self.guesses[s].iter().flatten() {
+                    distribution[g - 1] += 1;
+                }
+                Standing {
+                    strategy: s,
+                    mean: self.mean_over(s, 0..self.answers.len()),
+                    losses: self.guesses[s].iter().filter(|g| g.is_none()).count(),
+                    worst: self.guesses[s].iter().flatten().copied().max().unwrap_or(0),
+                    distribution,
+                }
+            })
+            .collect::<Vec<_>>();
+        standings.sort_by(|a, b| a.losses.cmp(&b.losses).then(a.mean.total_cmp(&b.mean)));
+        standings
+    }
+
+    /// The answers on which the strategies didn't all take the same number of guesses.
+    pub fn differences(&self) -> impl Iterator<Item = (Word, Vec<Option<usize>>)> + '_ {
+        (0..self.answers.len()).filter_map(|i| {
+            let row = self.guesses.iter().map(|g| g[i]).collect::<Vec<_>>();
+            row.iter()
+                .any(|&g| g != row[0])
+                .then(|| (self.answers[i], row))
+        })
+    }
+}
+
+impl fmt::Display for Standing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "mean {:.4}, worst {}, lost {}, distribution {:?}",
+            self.mean, self.worst, self.losses, self.distribution
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{squeeze::grade, word_from_str};
+
+    use super::*;
+
+    #[test]
+    fn compares_strategies() {
+        let w = |s: &str| word_from_str(s.as_bytes()).unwrap();
+        let answers = [
+            "fight", "light", "might", "night", "right", "sight", "tight",
+        ]
+        .map(w);
+        let words = ["flash", "mirth", "sonly"].map(w);
+        let greedy = Greedy {
+            words: &words,
+            metric: Metric::Entropy,
+            opener: None,
+            rules: Rules::default(),
+            candidates_only: false,
+        };
+        let naive = Greedy {
+            candidates_only: true,
+            ..greedy
+        };
+        assert_eq!(Strategy::<4>::name(&naive), "entropy/candidates");
+        // guessing candidates in turn finds the last one only on the seventh guess
+        let rules = Rules::default();
+        assert_eq!(play::<4>(&naive, answers[6], &answers, &rules), Ok(None));
+
+        let results = run::<4>(&[&greedy, &naive], &answers, &rules, 3).unwrap();
+        assert_eq!(
+            results.guesses[1],
+            [1, 2, 3, 4, 5, 6, 0].map(|g| (g > 0).then_some(g))
+        );
+        let h = results.head_to_head(0, 1);
+        assert_eq!(h.wins + h.losses + h.ties, answers.len());
+        assert_eq!(results.leaderboard()[0].strategy, 0);
+        assert_eq!(
+            HeadToHead {
+                wins: 5,
+                losses: 0,
+                ties: 2
+            }
+            .p_value(),
+            2.0 / 32.0
+        );
+        let (lo, hi) = results.bootstrap(0, 1, 100, &mut Rng::new(1));
+        // too few answers to be sure
+        assert!(lo < 0.0 && 0.0 < hi);
+        let wins = results.subset_wins(7, 10, &mut Rng::new(1));
+        assert_eq!(wins, [1.0, 0.0]);
+
+        // dropping the f found by `flash` breaks hard mode
+        let follow = Follow {
+            name: "follow".to_owned(),
+            tree: Tree {
+                guess: w("flash"),
+                branches: vec![(
+                    grade(w("flash"), answers[0]),
+                    Tree {
+                        guess: w("mirth"),
+                        branches: Vec::new(),
+                    },
+                )],
+            },
+        };
+        let hard = Rules {
+            hard: true,
+            ..rules
+        };
+        assert_eq!(
+            play::<4>(&follow, answers[0], &answers, &rules),
+            Ok(Some(3))
+        );
+        assert!(play::<4>(&follow, answers[0], &answers, &hard).is_err());
+        assert!(run::<4>(&[&follow], &answers, &hard, 2).is_err());
+    }
+}
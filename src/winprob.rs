@@ -0,0 +1,183 @@
+use std::{
+    collections::HashMap,
+    mem,
+    simd::{LaneCount, SupportedLaneCount},
+};
+
+use crate::{equiv::undominated, rules::Rules, Word};
+
+/// Finds the chance of solving within some number of guesses under the best play, by searching
+/// the game tree. With up to `exact_below` candidates every guess is tried, so the answer is
+/// exact; with more, only the `width` guesses leaving the least entropy are, giving a lower bound.
+/// Guesses are graded under `rules`; their guess limit is not used, as the guesses left are given.
+pub struct WinProb<'a> {
+    words: &'a [Word],
+    width: usize,
+    exact_below: usize,
+    rules: Rules,
+    memo: HashMap<(Vec<Word>, usize), f32>,
+}
+
+/// How many different grades `guess` can get against `candidates`.
+fn n_grades<const L: usize>(guess: Word, candidates: &[Word], rules: &Rules) -> usize
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    rules
+        .grade_counts::<L>(guess, candidates)
+        .into_iter()
+        .filter(|&n| n > 0)
+        .count()
+}
+
+impl<'a> WinProb<'a> {
+    pub fn new(words: &'a [Word], width: usize, exact_below: usize, rules: Rules) -> WinProb<'a> {
+        WinProb {
+            words,
+            width,
+            exact_below,
+            rules,
+            memo: HashMap::new(),
+        }
+    }
+
+    /// The guesses worth trying against `candidates`, candidates first.
+    fn guesses<const L: usize>(&self, candidates: &[Word]) -> Vec<Word>
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        if candidates.len() <= self.exact_below {
+            // dominated guesses can never do better
+            let pool = candidates
+                .iter()
+                .chain(self.words)
+                .copied()
+                .collect::<Vec<_>>();
+            return undominated::<L>(&pool, candidates, &self.rules);
+        }
+        let mut scored = candidates
+            .iter()
+            .chain(self.words)
+            .map(|&w| (w, self.rules.entropy_after::<L>(w, candidates)))
+            .collect::<Vec<_>>();
+        scored.sort_by(|a, b| a.1.total_cmp(&b.1));
+        scored.dedup_by_key(|&mut (w, _)| w);
+        scored
+            .into_iter()
+            .take(self.width)
+            .map(|(w, _)| w)
+            .collect()
+    }
+
+    /// The chance of solving within `guesses_left` guesses, playing `guess` now and the best
+    /// guess after that.
+    pub fn prob_after<const L: usize>(
+        &mut self,
+        guess: Word,
+        candidates: &[Word],
+        guesses_left: usize,
+    ) -> f32
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        let n = candidates.len() as f32;
+        match guesses_left {
+            0 => return 0.0,
+            1 => return candidates.contains(&guess) as usize as f32 / n,
+            // every grade but all green leaves one guess, which finds one answer
+            2 => return n_grades::<L>(guess, candidates, &self.rules) as f32 / n,
+            _ => {}
+        }
+        let all_green = self.rules.all_green() as usize;
+        let mut buckets = self.rules.bucket::<L>(guess, candidates);
+        let mut total = buckets[all_green].len() as f32;
+        for (graded, b) in buckets.iter_mut().enumerate() {
+            if b.is_empty() || graded == all_green {
+                continue;
+            }
+            let b = mem::take(b);
+            total += self.prob::<L>(&b, guesses_left - 1) * b.len() as f32;
+        }
+        total / n
+    }
+
+    /// The chance of solving within `guesses_left` guesses from `candidates` under the best play.
+    pub fn prob<const L: usize>(&mut self, candidates: &[Word], guesses_left: usize) -> f32
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        match (candidates.len(), guesses_left) {
+            (0, _) | (_, 0) => return 0.0,
+            (1, _) => return 1.0,
+            (n, 1) => return 1.0 / n as f32,
+            _ => {}
+        }
+        let key = (candidates.to_vec(), guesses_left);
+        if let Some(&p) = self.memo.get(&key) {
+            return p;
+        }
+        let mut best = 0.0f32;
+        for guess in self.guesses::<L>(candidates) {
+            best = best.max(self.prob_after::<L>(guess, candidates, guesses_left));
+            if best >= 1.0 {
+                break;
+            }
+        }
+        self.memo.insert(key, best);
+        best
+    }
+
+    /// The `top` guesses with the best chance of solving within `guesses_left`, best first.
+    pub fn rank<const L: usize>(
+        &mut self,
+        candidates: &[Word],
+        guesses_left: usize,
+        top: usize,
+    ) -> Vec<(Word, f32)>
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        let mut ranked = self
+            .guesses::<L>(candidates)
+            .into_iter()
+            .map(|g| (g, self.prob_after::<L>(g, candidates, guesses_left)))
+            .collect::<Vec<_>>();
+        // stable, so candidates stay ahead of other guesses which do as well
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ranked.truncate(top);
+        ranked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::word_from_str;
+
+    use super::*;
+
+    #[test]
+    fn small_games() {
+        let w = |s: &str| word_from_str(s.as_bytes()).unwrap();
+        let candidates = [
+            "fight", "light", "might", "night", "right", "sight", "tight",
+        ]
+        .map(w);
+        let words = ["flash", "mirth", "sonly"].map(w);
+        let mut solver = WinProb::new(&words, 10, 100, Rules::default());
+
+        assert_eq!(solver.prob::<4>(&candidates, 1), 1.0 / 7.0);
+        // a candidate tells nothing about the others, so 2 guesses find at most 2 of 7
+        assert_eq!(
+            solver.prob_after::<4>(candidates[0], &candidates, 2),
+            2.0 / 7.0
+        );
+        // `flash` picks out f, l and s, leaving one of four for the last guess
+        assert_eq!(
+            solver.prob_after::<4>(w("flash"), &candidates, 2),
+            4.0 / 7.0
+        );
+        assert!(solver.prob::<4>(&candidates, 3) > 4.0 / 7.0);
+        assert_eq!(solver.prob::<4>(&candidates, 7), 1.0);
+        assert_eq!(solver.rank::<4>(&candidates, 7, 1)[0].1, 1.0);
+    }
+}